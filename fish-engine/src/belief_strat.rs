@@ -0,0 +1,209 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+use crate::{
+    cards::{Card, Cards, Rank},
+    common_strat::update_hand_on_announcement,
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// How many rounds of proportional fitting to spend balancing the belief
+/// matrix against the hand sizes.
+const FITTING_ROUNDS: usize = 32;
+
+/// The expected number of cards of each rank in every hand and in the pool,
+/// indexed by `Rank as usize`. The believer's own hand is known exactly.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi))]
+pub struct Beliefs {
+    pub players: Vec<[f64; 13]>,
+    pub pool: [f64; 13],
+}
+
+impl Beliefs {
+    fn empty(num_players: usize) -> Self {
+        Beliefs {
+            players: vec![[0.0; 13]; num_players],
+            pool: [0.0; 13],
+        }
+    }
+    /// How many cards of `rank` we expect `pid` to hand over when asked.
+    pub fn expected(&self, pid: PlayerId, rank: Rank) -> f64 {
+        self.players[pid.0 as usize][rank as usize]
+    }
+}
+
+/// Keeps a probability distribution over where every unseen card is, and asks
+/// for whatever is expected to bring in the most cards.
+///
+/// Cards that are publicly known to be somewhere stay there. The rest are
+/// spread over every hand and the pool that could hold them, and then scaled
+/// until each hand holds as many cards as it is known to have.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bayes {
+    pid: PlayerId,
+    hand: Cards,
+    beliefs: Beliefs,
+    config: BayesConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BayesConfig {
+    /// How many cards revealing a rank we hold is worth giving up, since the
+    /// others will come asking for it.
+    pub reveal_cost: f64,
+}
+
+impl Default for BayesConfig {
+    fn default() -> Self {
+        BayesConfig { reveal_cost: 1.0 }
+    }
+}
+
+impl ConfigurableStrategy for Bayes {
+    type Config = BayesConfig;
+
+    fn init(pid: PlayerId, config: BayesConfig) -> Self {
+        Bayes {
+            pid,
+            hand: Cards::empty(),
+            beliefs: Beliefs::empty(0),
+            config,
+        }
+    }
+}
+
+impl Bayes {
+    fn held(&self, rank: Rank) -> u32 {
+        self.hand.intersection(rank.in_all_suits()).num()
+    }
+
+    /// The cards we expect to get by asking `who` for `rank`, less what it
+    /// costs to tell the table that we hold `rank`.
+    fn score(&self, ctx: &Context, who: PlayerId, rank: Rank) -> f64 {
+        let revealed = ctx.knowledge().at_least(self.pid, rank) > 0;
+        let cost = if revealed {
+            0.0
+        } else {
+            self.config.reveal_cost
+        };
+        self.beliefs.expected(who, rank) - cost
+    }
+
+    fn update_beliefs(&mut self, ctx: &Context) {
+        let knowledge = ctx.knowledge();
+        let sizes = ctx
+            .players()
+            .map(|(_, p)| p.cards_on_hand as f64)
+            .chain([knowledge.pool_size() as f64])
+            .collect_vec();
+        let pool = sizes.len() - 1;
+
+        // Cards known to be in a hand, and where the rest could be.
+        let mut known = vec![[0.0; 13]; sizes.len()];
+        let mut allowed = vec![[false; 13]; sizes.len()];
+        let mut unseen = [0.0; 13];
+        // Ranks left out of the deck or already booked are in nobody's hand.
+        for rank in (knowledge.deck().ranks() - knowledge.booked()).iter() {
+            let r = rank as usize;
+            known[self.pid.0 as usize][r] = self.held(rank) as f64;
+            unseen[r] = (4 - self.held(rank)) as f64;
+            for (pid, p) in knowledge.players().filter(|&(pid, _)| pid != self.pid) {
+                let at_least = (p.at_least(rank) as f64).min(unseen[r]);
+                known[pid.0 as usize][r] = at_least;
+                unseen[r] -= at_least;
+                allowed[pid.0 as usize][r] = !p.has_none(rank);
+            }
+            allowed[pool][r] = true;
+        }
+
+        let room = sizes
+            .iter()
+            .zip(&known)
+            .enumerate()
+            .map(|(loc, (size, known))| {
+                if loc == self.pid.0 as usize {
+                    0.0
+                } else {
+                    (size - known.iter().sum::<f64>()).max(0.0)
+                }
+            })
+            .collect_vec();
+
+        let mut spread = allowed
+            .iter()
+            .map(|row| row.map(|a| if a { 1.0 } else { 0.0 }))
+            .collect_vec();
+        for _ in 0..FITTING_ROUNDS {
+            for r in 0..13 {
+                let total: f64 = spread.iter().map(|row| row[r]).sum();
+                if total > 0.0 {
+                    for row in &mut spread {
+                        row[r] *= unseen[r] / total;
+                    }
+                }
+            }
+            for (row, room) in spread.iter_mut().zip(&room) {
+                let total: f64 = row.iter().sum();
+                if total > 0.0 {
+                    for x in row {
+                        *x *= room / total;
+                    }
+                }
+            }
+        }
+
+        let mut beliefs = Beliefs::empty(pool);
+        for (loc, (known, spread)) in known.iter().zip(&spread).enumerate() {
+            let target = if loc == pool {
+                &mut beliefs.pool
+            } else {
+                &mut beliefs.players[loc]
+            };
+            for r in 0..13 {
+                target[r] = known[r] + spread[r];
+            }
+        }
+        self.beliefs = beliefs;
+    }
+}
+
+impl Strategy for Bayes {
+    fn deal_card(&mut self, _ctx: &Context, card: Card) {
+        self.hand = self.hand.add(card);
+    }
+
+    fn action(&mut self, ctx: &Context) -> Option<Action> {
+        self.update_beliefs(ctx);
+
+        let ranks = self.hand.ranks();
+        let opponents = ctx
+            .players()
+            .filter(|&(pid, p)| pid != self.pid && p.cards_on_hand > 0)
+            .map(|(pid, _)| pid);
+        let (ask_who, ask_for) =
+            opponents
+                .cartesian_product(ranks.to_vec())
+                .max_by(|&(a, ra), &(b, rb)| {
+                    let gain_a = self.score(ctx, a, ra);
+                    let gain_b = self.score(ctx, b, rb);
+                    gain_a
+                        .total_cmp(&gain_b)
+                        .then(self.held(ra).cmp(&self.held(rb)))
+                })?;
+
+        Some(Action { ask_who, ask_for })
+    }
+
+    fn react(&mut self, ctx: &Context, ann: Announcement) {
+        update_hand_on_announcement(self.pid, ann, &mut self.hand);
+        self.update_beliefs(ctx);
+    }
+
+    fn beliefs(&self) -> Option<&Beliefs> {
+        Some(&self.beliefs)
+    }
+}
@@ -1,16 +1,18 @@
-use std::{
-    fmt::{Debug, Display},
-    ops::Range,
-};
+use std::fmt::{Debug, Display};
 
 use bitflags::bitflags;
+use fastrand::Rng;
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, FromRepr};
-use typeshare::typeshare;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
 
-#[derive(Debug, Clone, Copy, Serialize, EnumIter, FromRepr)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter, FromRepr)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 #[repr(u64)]
 pub enum Rank {
     RA = 0,
@@ -58,7 +60,12 @@ impl Ranks {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+/// Ranks are written out as a list wherever they are serialized.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(typescript_custom_section)]
+const TS_RANKS: &str = "export type Ranks = Rank[];";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[repr(u64)]
 pub enum Suit {
     Spades = 0,
@@ -75,7 +82,8 @@ bitflags! {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 pub struct Card(pub u64);
 
 impl Card {
@@ -161,11 +169,15 @@ impl Display for Card {
     }
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Cards {
     cards: u64,
 }
 
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(typescript_custom_section)]
+const TS_CARDS: &str = "export type Cards = Card[];";
+
 impl Serialize for Cards {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -175,6 +187,23 @@ impl Serialize for Cards {
     }
 }
 
+impl<'de> Deserialize<'de> for Cards {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<Card>::deserialize(deserializer)?
+            .into_iter()
+            .try_fold(Cards::empty(), |cs, c| {
+                if c.0 < 52 {
+                    Ok(cs.add(c))
+                } else {
+                    Err(serde::de::Error::custom(format!("{} is not a card", c.0)))
+                }
+            })
+    }
+}
+
 impl Cards {
     pub fn empty() -> Self {
         Cards { cards: 0 }
@@ -184,17 +213,34 @@ impl Cards {
             cards: (1 << 52) - 1,
         }
     }
-    pub fn all_bounded(n: u64) -> Self {
-        assert!((0..=13).contains(&n));
+    /// A reduced deck with only the first `n` ranks in every suit, or `None`
+    /// if there are not `n` ranks to choose from.
+    pub fn all_bounded(n: u64) -> Option<Self> {
+        if !(0..=13).contains(&n) {
+            return None;
+        }
 
         let mask = (1 << n) - 1;
         let cards = mask | (mask << 13) | (mask << (13 * 2)) | (mask << (13 * 3));
 
-        Cards { cards }
+        Some(Cards { cards })
+    }
+    /// The cards as a bitset, with bit `i` set for `Card(i)`.
+    pub fn bits(self) -> u64 {
+        self.cards
+    }
+    /// The inverse of [`Cards::bits`], or `None` if a bit past the last card
+    /// is set.
+    pub fn from_bits(bits: u64) -> Option<Self> {
+        if bits >> 52 != 0 {
+            return None;
+        }
+        Some(Cards { cards: bits })
     }
     pub fn is_empty(self) -> bool {
         self.cards == 0
     }
+    #[allow(clippy::should_implement_trait)]
     pub fn add(self, c: Card) -> Self {
         Cards {
             cards: self.cards | (1 << c.0),
@@ -224,12 +270,18 @@ impl Cards {
     pub fn has(self, c: Card) -> bool {
         !self.intersection(Cards::empty().add(c)).is_empty()
     }
+    /// The ranks of which at least one card is in `self`.
+    pub fn ranks(self) -> Ranks {
+        self.iter()
+            .map(|c| c.rank())
+            .fold(Ranks::empty(), |rs, r| rs | r.into())
+    }
     pub fn num(self) -> u32 {
-        assert_eq!(self.cards.count_ones() as usize, self.iter().count());
+        debug_assert_eq!(self.cards.count_ones() as usize, self.iter().count());
 
         self.cards.count_ones() as _
     }
-    pub fn choose_random(self) -> Option<Card> {
+    pub fn choose_random(self, rng: &Rng) -> Option<Card> {
         // fn choose(r: Range<u64>) -> u64 {
         //     let len = r.end - r.start;
 
@@ -243,7 +295,7 @@ impl Cards {
             return None;
         }
 
-        let i = fastrand::u64(0..self.num() as u64);
+        let i = rng.u64(0..self.num() as u64);
 
         // let i = choose(0..self.num() as u64);
 
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+use crate::{error::GameError, rules::RuleSet, strategy::StratBuilder, Game};
+
+/// Everything needed to set up a game, in a form a setup screen can send.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(from_wasm_abi))]
+#[serde(deny_unknown_fields)]
+pub struct GameConfig {
+    /// The strategy at every seat. There are as many players as seats.
+    pub seats: Vec<StratBuilder>,
+    pub starting_cards: u32,
+    #[serde(default)]
+    pub rules: RuleSet,
+    /// Picked at random when left out.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+impl Game {
+    /// Sets up the game `config` describes, or explains what is wrong with it.
+    pub fn from_config(config: GameConfig) -> Result<Game, GameError> {
+        let game = match config.seed {
+            Some(seed) => Game::with_seed(seed, config.starting_cards, config.seats)?,
+            None => Game::new(config.starting_cards, config.seats)?,
+        };
+        Ok(game.with_rules(config.rules))
+    }
+}
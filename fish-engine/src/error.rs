@@ -0,0 +1,62 @@
+use derive_more::Display;
+
+use crate::{
+    cards::Rank,
+    strategy::{Action, PlayerId},
+};
+
+/// Everything that can go wrong while setting up or stepping a
+/// [`Game`](crate::Game). None of these are fatal to the process: a caller can
+/// disqualify the offending bot, log the error, and carry on.
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum GameError {
+    #[display(fmt = "player {} asked themselves for cards", "player.0")]
+    AskedSelf { player: PlayerId },
+    #[display(fmt = "player {} asked for {:?} without holding any", "player.0", rank)]
+    RankNotHeld { player: PlayerId, rank: Rank },
+    #[display(
+        fmt = "player {} asked player {}, who has no cards",
+        "player.0",
+        "asked.0"
+    )]
+    AskedEmptyHand { player: PlayerId, asked: PlayerId },
+    #[display(fmt = "there is no player {}", "player.0")]
+    UnknownPlayer { player: PlayerId },
+    #[display(fmt = "player {} had a legal action but did not take one", "player.0")]
+    NoAction { player: PlayerId },
+    #[display(fmt = "no player is waiting to make an ask")]
+    NoActionExpected,
+    #[display(
+        fmt = "player {} tried to ask on the turn of player {}",
+        "player.0",
+        "who.0"
+    )]
+    NotYourTurn { player: PlayerId, who: PlayerId },
+    #[display(
+        fmt = "player {} is played by a strategy, which makes its own asks",
+        "player.0"
+    )]
+    NotExternal { player: PlayerId },
+    #[display(
+        fmt = "player {} wants to see every hand, which this game does not allow",
+        "player.0"
+    )]
+    FullInformation { player: PlayerId },
+    #[display(
+        fmt = "replayed ask {} was {:?}, but the strategy asked {:?}",
+        step,
+        expected,
+        played
+    )]
+    ReplayDiverged {
+        step: usize,
+        expected: Action,
+        played: Action,
+    },
+    #[display(fmt = "invariant violated: {}", _0)]
+    InvariantViolation(String),
+    #[display(fmt = "invalid configuration: {}", _0)]
+    InvalidConfig(String),
+}
+
+impl std::error::Error for GameError {}
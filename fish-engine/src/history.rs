@@ -0,0 +1,148 @@
+use crate::{
+    cards::{Cards, Ranks},
+    knowledge::PublicKnowledge,
+    strategy::{Announcement, Strat},
+    Game, GameStage,
+};
+
+/// Everything that changes between two steps. Cards are single bitsets, so
+/// apart from the strategies a snapshot is a handful of words.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    pool: Cards,
+    hands: Vec<Cards>,
+    books: Vec<Ranks>,
+    strategies: Vec<Strat>,
+    stage: GameStage,
+    rng: u64,
+    ctx_rng: u64,
+    knowledge: PublicKnowledge,
+    announcements: usize,
+}
+
+/// The steps a [`Game`] has taken since [`Game::enable_history`], so it can be
+/// moved backwards and forwards through them.
+#[derive(Debug)]
+pub struct History {
+    /// The state after each step, starting with the state history was
+    /// enabled in.
+    snapshots: Vec<Snapshot>,
+    cursor: usize,
+    /// Every announcement up to the latest snapshot, including those that are
+    /// currently undone.
+    log: Vec<Announcement>,
+    /// Bumped whenever undone steps are dropped, since the announcements
+    /// after that point are no longer the ones that were made before.
+    timeline: u32,
+}
+
+impl History {
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+    /// The step the game is currently at, counted from when history was
+    /// enabled.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+    pub(crate) fn timeline(&self) -> u32 {
+        self.timeline
+    }
+}
+
+impl Game {
+    /// Starts recording a snapshot after every step. Recording starts over if
+    /// it was already enabled.
+    pub fn enable_history(&mut self) {
+        self.history = Some(History {
+            snapshots: vec![self.snapshot()],
+            cursor: 0,
+            log: self.announcements.clone(),
+            timeline: 0,
+        });
+    }
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+    pub fn history(&self) -> Option<&History> {
+        self.history.as_ref()
+    }
+    /// Goes back one step. Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.as_ref().map(|h| h.cursor) {
+            Some(cursor) if cursor > 0 => self.seek(cursor - 1),
+            _ => false,
+        }
+    }
+    /// Goes forward one undone step. Returns `false` if there is nothing to
+    /// redo.
+    pub fn redo(&mut self) -> bool {
+        match self.history.as_ref().map(|h| h.cursor) {
+            Some(cursor) => self.seek(cursor + 1),
+            None => false,
+        }
+    }
+    /// Moves to the state after `step` recorded steps. Returns `false` if that
+    /// step has not been recorded.
+    pub fn seek(&mut self, step: usize) -> bool {
+        let Some(history) = &mut self.history else {
+            return false;
+        };
+        let Some(snapshot) = history.snapshots.get(step) else {
+            return false;
+        };
+        history.cursor = step;
+
+        self.pool = snapshot.pool;
+        for (i, p) in self.players.iter_mut().enumerate() {
+            p.hand = snapshot.hands[i];
+            p.books = snapshot.books[i];
+            p.strategy = snapshot.strategies[i].clone();
+        }
+        self.stage = snapshot.stage.clone();
+        self.rng.seed(snapshot.rng);
+        self.ctx.rng().seed(snapshot.ctx_rng);
+        *self.ctx.knowledge_mut() = snapshot.knowledge.clone();
+        self.announcements = history.log[..snapshot.announcements].to_vec();
+        self.ctx.update(&self.players);
+
+        true
+    }
+    /// Records the step that was just taken, dropping anything that had been
+    /// undone.
+    pub(crate) fn record_step(&mut self) {
+        if self.history.is_none() {
+            return;
+        }
+        let snapshot = self.snapshot();
+        let Some(history) = &mut self.history else {
+            return;
+        };
+
+        history.snapshots.truncate(history.cursor + 1);
+        let prev = history.snapshots[history.cursor].announcements;
+        if history.log.len() > prev {
+            history.timeline += 1;
+        }
+        history.log.truncate(prev);
+        history.log.extend_from_slice(&self.announcements[prev..]);
+        history.snapshots.push(snapshot);
+        history.cursor += 1;
+    }
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pool: self.pool,
+            hands: self.players.iter().map(|p| p.hand).collect(),
+            books: self.players.iter().map(|p| p.books).collect(),
+            strategies: self.players.iter().map(|p| p.strategy.clone()).collect(),
+            stage: self.stage.clone(),
+            rng: self.rng.get_seed(),
+            ctx_rng: self.ctx.rng().get_seed(),
+            knowledge: self.ctx.knowledge().clone(),
+            announcements: self.announcements.len(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::Card,
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// A seat played by a person. The game stops and
+/// [awaits their input](crate::GameStage::AwaitingInput) on their turn, and
+/// their asks come in through [`Game::submit_action`](crate::Game::submit_action).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Human {
+    pid: PlayerId,
+}
+
+impl ConfigurableStrategy for Human {
+    type Config = ();
+
+    fn init(pid: PlayerId, _config: ()) -> Self {
+        Human { pid }
+    }
+}
+
+impl Strategy for Human {
+    fn deal_card(&mut self, _ctx: &Context, _card: Card) {}
+
+    fn action(&mut self, _ctx: &Context) -> Option<Action> {
+        None
+    }
+
+    fn react(&mut self, _ctx: &Context, _ann: Announcement) {}
+
+    fn is_external(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,195 @@
+use instant::{Duration, Instant};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Card, Cards},
+    common_strat::update_hand_on_announcement,
+    sim::{Rollout, SimState},
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// Information-set Monte Carlo tree search: every iteration deals the unseen
+/// cards at random, walks down a tree of asks shared between all the deals,
+/// and plays the rest of the game out with the configured rollout. The ask
+/// with the best average book gain is the one made.
+///
+/// Turns belong to whoever is asking in the dealt game, and every player is
+/// assumed to chase their own books.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ismcts {
+    pid: PlayerId,
+    hand: Cards,
+    config: IsmctsConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IsmctsConfig {
+    /// How many games to play out per ask.
+    pub iterations: u32,
+    /// Stop searching after this many milliseconds, even if not all
+    /// iterations are done. Games are only reproducible without one.
+    pub time_budget_ms: Option<u64>,
+    /// How eagerly to try asks that have not paid off so far.
+    pub exploration: f64,
+    /// How to play the game out past the tree.
+    pub rollout: Rollout,
+}
+
+impl Default for IsmctsConfig {
+    fn default() -> Self {
+        IsmctsConfig {
+            iterations: 500,
+            time_budget_ms: None,
+            exploration: 0.7,
+            rollout: Rollout::Random,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    /// The ask leading here, and who made it.
+    action: Option<(PlayerId, Action)>,
+    children: Vec<usize>,
+    visits: u32,
+    /// How many times this ask could have been made when its parent was
+    /// visited.
+    available: u32,
+    /// The books gained by whoever made the ask, summed over all visits.
+    reward: f64,
+}
+
+impl Node {
+    fn new(action: Option<(PlayerId, Action)>) -> Self {
+        Node {
+            action,
+            children: vec![],
+            visits: 0,
+            available: 1,
+            reward: 0.0,
+        }
+    }
+    fn ucb(&self, exploration: f64) -> f64 {
+        let visits = self.visits as f64;
+        self.reward / visits + exploration * ((self.available as f64).ln() / visits).sqrt()
+    }
+}
+
+impl ConfigurableStrategy for Ismcts {
+    type Config = IsmctsConfig;
+
+    fn init(pid: PlayerId, config: IsmctsConfig) -> Self {
+        Ismcts {
+            pid,
+            hand: Cards::empty(),
+            config,
+        }
+    }
+}
+
+impl Ismcts {
+    fn search(&self, ctx: &Context) -> Option<Action> {
+        let rng = ctx.rng();
+        let deadline = self
+            .config
+            .time_budget_ms
+            .map(|ms| Instant::now() + Duration::from_millis(ms));
+
+        let mut tree = vec![Node::new(None)];
+        for _ in 0..self.config.iterations {
+            if deadline.is_some_and(|d| Instant::now() >= d) {
+                break;
+            }
+
+            let mut state = SimState::sample(ctx, self.pid, self.hand, rng);
+            let start = state.book_counts();
+            let mut path = vec![0];
+            let mut node = 0;
+
+            while !state.is_done() {
+                let who = state.who_next;
+                let legal = state.legal_actions(who).collect_vec();
+                let children = tree[node]
+                    .children
+                    .iter()
+                    .copied()
+                    .filter(|&c| matches!(tree[c].action, Some((p, a)) if p == who && legal.contains(&a)))
+                    .collect_vec();
+
+                let untried = legal
+                    .iter()
+                    .filter(|&&a| !children.iter().any(|&c| tree[c].action == Some((who, a))))
+                    .copied()
+                    .collect_vec();
+                if let Some(&action) = crate::pick(rng, &untried) {
+                    tree.push(Node::new(Some((who, action))));
+                    let child = tree.len() - 1;
+                    tree[node].children.push(child);
+                    path.push(child);
+                    state.apply(action, rng);
+                    break;
+                }
+
+                for &c in &children {
+                    tree[c].available += 1;
+                }
+                let Some(best) = children.iter().copied().max_by(|&a, &b| {
+                    let a = tree[a].ucb(self.config.exploration);
+                    let b = tree[b].ucb(self.config.exploration);
+                    a.total_cmp(&b)
+                }) else {
+                    break;
+                };
+                let (_, action) = tree[best].action.expect("only the root has no action");
+                path.push(best);
+                node = best;
+                state.apply(action, rng);
+            }
+
+            state.play_out(self.config.rollout, rng);
+            let gains = state
+                .book_counts()
+                .iter()
+                .zip(&start)
+                .map(|(end, start)| (end - start) as f64 / 13.0)
+                .collect_vec();
+            for &n in &path {
+                tree[n].visits += 1;
+                if let Some((who, _)) = tree[n].action {
+                    tree[n].reward += gains[who.0 as usize];
+                }
+            }
+        }
+
+        tree[0]
+            .children
+            .iter()
+            .max_by(|&&a, &&b| {
+                let (a, b) = (&tree[a], &tree[b]);
+                (a.reward / a.visits as f64)
+                    .total_cmp(&(b.reward / b.visits as f64))
+                    .then(a.visits.cmp(&b.visits))
+            })
+            .and_then(|&c| tree[c].action)
+            .map(|(_, action)| action)
+    }
+}
+
+impl Strategy for Ismcts {
+    fn deal_card(&mut self, _ctx: &Context, card: Card) {
+        self.hand = self.hand.add(card);
+    }
+
+    fn action(&mut self, ctx: &Context) -> Option<Action> {
+        self.search(ctx).or_else(|| {
+            let actions = ctx.legal_actions(self.pid, self.hand).collect_vec();
+            crate::pick(ctx.rng(), &actions).copied()
+        })
+    }
+
+    fn react(&mut self, _ctx: &Context, ann: Announcement) {
+        update_hand_on_announcement(self.pid, ann, &mut self.hand);
+    }
+}
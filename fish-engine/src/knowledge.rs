@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    cards::{Cards, Rank, Ranks},
+    strategy::{Announcement, PlayerId, Response, SkipReason},
+};
+
+/// What everyone at the table can deduce about one player's hand.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerKnowledge {
+    /// The fewest cards of each rank the player can be holding, indexed by
+    /// `Rank as usize`.
+    at_least: [u32; 13],
+    /// Ranks the player definitely holds none of.
+    #[serde(
+        serialize_with = "crate::ranks_to_vec",
+        deserialize_with = "crate::ranks_from_vec"
+    )]
+    none: Ranks,
+}
+
+impl Default for PlayerKnowledge {
+    fn default() -> Self {
+        PlayerKnowledge {
+            at_least: [0; 13],
+            none: Ranks::empty(),
+        }
+    }
+}
+
+impl PlayerKnowledge {
+    pub fn at_least(&self, rank: Rank) -> u32 {
+        self.at_least[rank as usize]
+    }
+    pub fn has_none(&self, rank: Rank) -> bool {
+        self.none.contains(rank.into())
+    }
+    /// Ranks the player is known to hold at least one of.
+    pub fn known_ranks(&self) -> Ranks {
+        Rank::iter()
+            .filter(|&r| self.at_least(r) > 0)
+            .fold(Ranks::empty(), |rs, r| rs | r.into())
+    }
+    /// How many of the player's cards are accounted for by `at_least`.
+    pub fn known_cards(&self) -> u32 {
+        self.at_least.iter().sum()
+    }
+    fn holds(&mut self, rank: Rank, at_least: u32) {
+        self.at_least[rank as usize] = self.at_least[rank as usize].max(at_least);
+        self.none.remove(rank.into());
+    }
+    fn gave_away(&mut self, rank: Rank) {
+        self.at_least[rank as usize] = 0;
+        self.none.insert(rank.into());
+    }
+    /// After drawing an unseen card, the player could hold any rank that is
+    /// still in play.
+    fn drew(&mut self, booked: Ranks) {
+        self.none &= booked;
+    }
+}
+
+/// Folds the announcement stream into facts about every player's hand, so that
+/// strategies can count cards without each reimplementing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKnowledge {
+    players: Vec<PlayerKnowledge>,
+    #[serde(
+        serialize_with = "crate::ranks_to_vec",
+        deserialize_with = "crate::ranks_from_vec"
+    )]
+    booked: Ranks,
+    starting_cards: u32,
+    deck: Cards,
+    pool_size: u32,
+}
+
+impl PublicKnowledge {
+    pub fn new(num_players: usize, starting_cards: u32) -> Self {
+        PublicKnowledge {
+            players: vec![Default::default(); num_players],
+            booked: Ranks::empty(),
+            starting_cards,
+            deck: Cards::all(),
+            pool_size: Cards::all().num(),
+        }
+    }
+    /// Every card in the game, whether it has been dealt or not.
+    pub fn deck(&self) -> Cards {
+        self.deck
+    }
+    pub(crate) fn set_deck(&mut self, deck: Cards) {
+        self.deck = deck;
+        self.pool_size = deck.num();
+    }
+    pub fn player(&self, pid: PlayerId) -> &PlayerKnowledge {
+        &self.players[pid.0 as usize]
+    }
+    pub fn players(&self) -> impl Iterator<Item = (PlayerId, &PlayerKnowledge)> {
+        self.players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (PlayerId(i as _), p))
+    }
+    pub fn at_least(&self, pid: PlayerId, rank: Rank) -> u32 {
+        self.player(pid).at_least(rank)
+    }
+    pub fn has_none(&self, pid: PlayerId, rank: Rank) -> bool {
+        self.player(pid).has_none(rank)
+    }
+    /// Players known to hold at least one card of `rank`.
+    pub fn known_holders(&self, rank: Rank) -> impl Iterator<Item = PlayerId> + '_ {
+        self.players()
+            .filter(move |(_, p)| p.at_least(rank) > 0)
+            .map(|(pid, _)| pid)
+    }
+    /// Ranks for which every card has been collected into a book.
+    pub fn booked(&self) -> Ranks {
+        self.booked
+    }
+    pub fn pool_size(&self) -> u32 {
+        self.pool_size
+    }
+    pub fn observe(&mut self, ann: Announcement) {
+        match ann {
+            Announcement::Action {
+                player_asking,
+                player_asked,
+                asked_for,
+                response,
+            } => {
+                let before = self.at_least(player_asking, asked_for).max(1);
+                match response {
+                    Response::TakeThese { count } => {
+                        self.players[player_asking.0 as usize].holds(asked_for, before + count);
+                    }
+                    Response::GoFish => {
+                        self.players[player_asking.0 as usize].holds(asked_for, before);
+                        if self.pool_size > 0 {
+                            self.pool_size -= 1;
+                            self.players[player_asking.0 as usize].drew(self.booked);
+                        }
+                    }
+                }
+                self.players[player_asked.0 as usize].gave_away(asked_for);
+            }
+            Announcement::GotBook { book, .. } => {
+                self.booked |= book.into();
+                for p in &mut self.players {
+                    p.gave_away(book);
+                }
+            }
+            Announcement::DealComplete => {
+                self.pool_size = self
+                    .pool_size
+                    .saturating_sub(self.starting_cards * self.players.len() as u32);
+            }
+            Announcement::Drew { player } => {
+                self.pool_size = self.pool_size.saturating_sub(1);
+                self.players[player.0 as usize].drew(self.booked);
+            }
+            Announcement::PoolClaimed { player, count } => {
+                self.pool_size = self.pool_size.saturating_sub(count);
+                self.players[player.0 as usize].drew(self.booked);
+            }
+            Announcement::TurnSkipped {
+                player,
+                reason: SkipReason::EmptyHand,
+            } => {
+                for r in Rank::iter() {
+                    self.players[player.0 as usize].gave_away(r);
+                }
+            }
+            Announcement::TurnSkipped {
+                reason: SkipReason::NobodyToAsk,
+                ..
+            } => {}
+        }
+    }
+}
@@ -1,23 +1,48 @@
-use log::info;
-use serde::Serialize;
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
 
+pub mod belief_strat;
 pub mod cards;
 pub mod common_strat;
+pub mod config;
+pub mod error;
+pub mod history;
+pub mod human_strat;
+pub mod ismcts_strat;
+pub mod knowledge;
+pub mod memory_strat;
+pub mod observer;
+pub mod oracle_strat;
+pub mod outcome;
+pub mod pimc_strat;
 pub mod random_strat;
+pub mod registry;
+pub mod replay;
+pub mod rules;
+pub mod sim;
+pub mod solver;
 pub mod strategy;
+pub mod suggest;
+pub mod view;
 pub mod wiki_strat;
 
 use cards::{Card, Cards, Ranks};
+use error::GameError;
+use history::History;
+use observer::{GameObserver, Observers};
+use rules::RuleSet;
+use sim::{AutomaticMove, SimState};
 use strategy::{
-    Announcement, Context, PlayerId, PublicPlayerInfo, Response, Strat, StratBuilder, Strategy,
+    Action, Announcement, Context, PlayerId, Response, SkipReason, Strat, StratBuilder,
 };
-use typeshare::typeshare;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
 
-pub fn pick<T>(xs: &[T]) -> Option<&T> {
+pub fn pick<'a, T>(rng: &Rng, xs: &'a [T]) -> Option<&'a T> {
     if xs.is_empty() {
         return None;
     }
-    xs.get(fastrand::usize(0..xs.len()))
+    xs.get(rng.usize(0..xs.len()))
 
     // let mut dest = [0; (usize::BITS / u8::BITS) as usize];
 
@@ -32,13 +57,36 @@ where
     ranks.to_vec().serialize(serializer)
 }
 
-#[typeshare]
-#[derive(Debug, Serialize)]
+fn ranks_from_vec<'de, D>(deserializer: D) -> Result<Ranks, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Vec::<cards::Rank>::deserialize(deserializer)?
+        .into_iter()
+        .fold(Ranks::empty(), |rs, r| rs | r.into()))
+}
+
+/// Stores the full state of the generator, so that a saved game continues with
+/// exactly the same draws.
+fn rng_to_state<S>(rng: &Rng, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    rng.get_seed().serialize(serializer)
+}
+
+fn rng_from_state<'de, D>(deserializer: D) -> Result<Rng, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Rng::with_seed(u64::deserialize(deserializer)?))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Player {
     pub hand: Cards,
-    #[serde(serialize_with = "ranks_to_vec")]
+    #[serde(serialize_with = "ranks_to_vec", deserialize_with = "ranks_from_vec")]
     pub books: Ranks,
-    #[serde(skip)]
     pub strategy: Strat,
 }
 
@@ -61,108 +109,403 @@ impl Player {
     }
 }
 
-#[typeshare]
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Game {
+    seed: u64,
     starting_cards: u32,
+    rules: RuleSet,
+    #[serde(default = "Cards::all")]
+    deck: Cards,
+    #[serde(default)]
+    full_information: bool,
     pub pool: Cards,
     pub players: Vec<Player>,
     pub announcements: Vec<Announcement>,
     pub stage: GameStage,
-    #[serde(skip)]
+    #[serde(serialize_with = "rng_to_state", deserialize_with = "rng_from_state")]
+    rng: Rng,
     ctx: Context,
+    #[serde(skip)]
+    history: Option<History>,
+    #[serde(skip)]
+    observers: Observers,
 }
 
-#[typeshare]
-#[derive(Debug, Serialize, Clone, derive_more::IsVariant)]
+#[derive(Debug, Serialize, Deserialize, Clone, derive_more::IsVariant)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 #[serde(tag = "name", content = "content")]
 pub enum GameStage {
-    Dealing { who_next: PlayerId },
-    Playing { who_next: PlayerId },
-    Done { who_next: PlayerId },
+    Dealing {
+        who_next: PlayerId,
+    },
+    Playing {
+        who_next: PlayerId,
+    },
+    /// Waiting for [`Game::submit_action`] from a seat played from outside the
+    /// engine.
+    AwaitingInput {
+        who: PlayerId,
+    },
+    Done {
+        who_next: PlayerId,
+    },
+}
+
+impl GameStage {
+    /// The player whose turn it is while the game is being played.
+    pub fn whose_turn(&self) -> Option<PlayerId> {
+        match *self {
+            GameStage::Playing { who_next } => Some(who_next),
+            GameStage::AwaitingInput { who } => Some(who),
+            GameStage::Dealing { .. } | GameStage::Done { .. } => None,
+        }
+    }
+}
+
+/// What a single [`Game::step`] left the game in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepOutcome {
+    Continue,
+    /// Nothing more happens until `who` makes their ask through
+    /// [`Game::submit_action`].
+    AwaitingInput {
+        who: PlayerId,
+    },
+    Done,
 }
 
 impl Game {
-    pub fn new<const N: usize>(starting_cards: u32, players: [StratBuilder; N]) -> Self {
+    /// The fewest seats a game can be played with.
+    pub const MIN_PLAYERS: usize = 2;
+
+    pub fn new(
+        starting_cards: u32,
+        players: impl IntoIterator<Item = StratBuilder>,
+    ) -> Result<Self, GameError> {
+        Game::with_seed(fastrand::u64(..), starting_cards, players)
+    }
+    /// Creates a game where every random decision, both in dealing and in the
+    /// strategies, is derived from `seed`.
+    pub fn with_seed(
+        seed: u64,
+        starting_cards: u32,
+        players: impl IntoIterator<Item = StratBuilder>,
+    ) -> Result<Self, GameError> {
+        let players = players.into_iter().collect::<Vec<_>>();
         let num_players = players.len();
-        let ctx = Context::new(players.len());
+        Game::validate_config(Cards::all(), starting_cards, num_players)?;
+
+        let rng = Rng::with_seed(seed);
+        let ctx = Context::new(players.len(), starting_cards, Rng::with_seed(rng.u64(..)));
+        let first_dealer = PlayerId(rng.u32(0..num_players as u32));
 
-        Game {
+        Ok(Game {
+            seed,
             starting_cards,
+            rules: RuleSet::default(),
+            deck: Cards::all(),
+            full_information: false,
             pool: Cards::all(),
             players: players
                 .into_iter()
                 .enumerate()
-                .map(|(pid, s)| Player::new(s.init(PlayerId(pid as _))))
-                .collect(),
+                .map(|(pid, s)| Ok(Player::new(s.init(PlayerId(pid as _))?)))
+                .collect::<Result<_, GameError>>()?,
             announcements: vec![],
             stage: GameStage::Dealing {
-                who_next: PlayerId(fastrand::u32(0..num_players as u32)),
+                who_next: first_dealer,
             },
+            rng,
             ctx,
+            history: None,
+            observers: Observers::default(),
+        })
+    }
+    /// Notifies `observer` of everything that happens from now on.
+    pub fn add_observer(&mut self, observer: impl GameObserver + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+    /// Plays the game by `rules` instead of the [house rules](RuleSet::house).
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self.ctx.set_rules(rules);
+        self
+    }
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    /// Lets strategies that [see all hands](strategy::Strategy::sees_all_hands)
+    /// play. Without this, such a strategy stops the game with an error when
+    /// its turn comes, so that a fair game cannot be joined by a cheat.
+    pub fn allow_full_information(mut self) -> Self {
+        self.full_information = true;
+        self
+    }
+    pub fn allows_full_information(&self) -> bool {
+        self.full_information
+    }
+    /// Deals from `deck` instead of a full deck, such as one made with
+    /// [`Cards::all_bounded`]. Only possible before any card has been dealt.
+    pub fn with_deck(mut self, deck: Cards) -> Result<Self, GameError> {
+        if self.pool != self.deck || !self.announcements.is_empty() {
+            return Err(GameError::InvalidConfig(
+                "the deck can only be changed before dealing".to_string(),
+            ));
+        }
+        let whole_ranks = deck
+            .ranks()
+            .iter()
+            .fold(Cards::empty(), |cs, r| cs.union(r.in_all_suits()));
+        if deck != whole_ranks {
+            return Err(GameError::InvalidConfig(
+                "a deck must have every suit of the ranks in it".to_string(),
+            ));
         }
+        Game::validate_config(deck, self.starting_cards, self.players.len())?;
+        self.deck = deck;
+        self.pool = deck;
+        self.ctx.set_deck(deck);
+        Ok(self)
     }
-    pub fn check_validity(&self) {
+    pub fn deck(&self) -> Cards {
+        self.deck
+    }
+    pub fn starting_cards(&self) -> u32 {
+        self.starting_cards
+    }
+    /// The most seats that can each be dealt `starting_cards` from a full deck.
+    pub fn max_players(starting_cards: u32) -> usize {
+        if starting_cards == 0 {
+            return 0;
+        }
+        (Cards::all().num() / starting_cards) as usize
+    }
+    fn validate_config(
+        deck: Cards,
+        starting_cards: u32,
+        num_players: usize,
+    ) -> Result<(), GameError> {
+        if starting_cards == 0 {
+            return Err(GameError::InvalidConfig(
+                "players must be dealt at least one card".to_string(),
+            ));
+        }
+        if num_players < Game::MIN_PLAYERS {
+            return Err(GameError::InvalidConfig(format!(
+                "a game needs at least {} players, got {num_players}",
+                Game::MIN_PLAYERS
+            )));
+        }
+        if num_players as u32 * starting_cards > deck.num() {
+            return Err(GameError::InvalidConfig(format!(
+                "cannot deal {starting_cards} cards to each of {num_players} players from a deck of {}",
+                deck.num()
+            )));
+        }
+        Ok(())
+    }
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+    /// What the table as a whole knows about every hand.
+    pub fn knowledge(&self) -> &knowledge::PublicKnowledge {
+        self.ctx.knowledge()
+    }
+    pub fn check_validity(&self) -> Result<(), GameError> {
         let mut seen = self.pool;
 
-        for p in &self.players {
+        for (pid, p) in self.players.iter().enumerate() {
             if !self.pool.intersection(p.hand).is_empty() {
-                panic!("PLAYER HAD CARDS IN HAND THAT WERE SOME WHERE IN THE POOL");
+                return Err(GameError::InvariantViolation(format!(
+                    "player {pid} had cards in hand that were also in the pool"
+                )));
             }
             if !seen.intersection(p.hand).is_empty() {
-                panic!("PLAYER HAD CARDS IN HAND THAT WERE SOME WHERE ELSE");
+                return Err(GameError::InvariantViolation(format!(
+                    "player {pid} had cards in hand that were somewhere else"
+                )));
             }
             seen = p.hand.union(seen);
 
             for r in p.books.iter() {
                 let rank = r.in_all_suits();
                 if !seen.intersection(rank).is_empty() {
-                    panic!("PLAYER HAD CARDS IN HAND THAT WERE SOME WHERE ELSE");
+                    return Err(GameError::InvariantViolation(format!(
+                        "player {pid} had a book of {r:?} with cards that were somewhere else"
+                    )));
                 }
                 seen = rank.union(seen);
             }
         }
 
-        assert_eq!(seen, Cards::all());
+        if seen != self.deck {
+            return Err(GameError::InvariantViolation(format!(
+                "cards went missing: {}",
+                self.deck.remove(seen)
+            )));
+        }
+
+        Ok(())
     }
-    pub fn step(&mut self) {
+    pub fn step(&mut self) -> Result<StepOutcome, GameError> {
         self.ctx.update(&self.players);
 
         let prev_count = self.announcements.len();
-        self.step_inner();
+        let prev_stage = self.stage.clone();
+        self.step_inner()?;
+
+        Ok(self.finish_step(prev_count, prev_stage))
+    }
+    /// Makes the ask of `pid`, a seat played from outside the engine, such as
+    /// by a person while the game is [awaiting their
+    /// input](GameStage::AwaitingInput). Seats played by strategies make their
+    /// own asks in [`Game::step`].
+    pub fn submit_action(
+        &mut self,
+        pid: PlayerId,
+        action: Action,
+    ) -> Result<StepOutcome, GameError> {
+        match self.awaits_action() {
+            None => return Err(GameError::NoActionExpected),
+            Some(who) if who != pid => return Err(GameError::NotYourTurn { player: pid, who }),
+            Some(_) if !self.players[pid.0 as usize].strategy.is_external() => {
+                return Err(GameError::NotExternal { player: pid })
+            }
+            Some(_) => {}
+        }
+        self.check_action(pid, action)?;
 
+        self.ctx.update(&self.players);
+
+        let prev_count = self.announcements.len();
+        let prev_stage = self.stage.clone();
+        self.resolve_ask(pid, action)?;
+
+        Ok(self.finish_step(prev_count, prev_stage))
+    }
+    /// The player whose ask the next step will resolve, or `None` if the next
+    /// step happens without anyone making a decision.
+    pub fn awaits_action(&self) -> Option<PlayerId> {
+        match self.stage {
+            GameStage::Playing { who_next } if self.automatic_move(who_next).is_none() => {
+                Some(who_next)
+            }
+            GameStage::AwaitingInput { who } => Some(who),
+            _ => None,
+        }
+    }
+    fn finish_step(&mut self, prev_count: usize, prev_stage: GameStage) -> StepOutcome {
+        self.ctx.update(&self.players);
         for a in &self.announcements[prev_count..] {
+            self.ctx.observe(*a);
             for p in &mut self.players {
                 p.strategy.react(&self.ctx, *a);
             }
         }
-    }
-    fn check_books(&mut self, pid: PlayerId) {
-        let p = &mut self.players[pid.0 as usize];
 
-        let hand = p.hand;
-        let mut seen = Cards::empty();
+        if let (Some(from), Some(to)) = (prev_stage.whose_turn(), self.stage.whose_turn()) {
+            if from != to {
+                self.observers.turn_passed(from, to);
+            }
+        }
+        if !prev_stage.is_done() {
+            if let Some(outcome) = self.outcome() {
+                self.observers.game_finished(&outcome);
+            }
+        }
 
-        let ranks = hand
-            .iter()
-            .map(|c| c.rank())
-            .fold(Ranks::empty(), |rs, r| rs | r.into());
+        self.record_step();
 
-        for rank in ranks.iter() {
-            if rank.in_all_suits().intersection(hand) == rank.in_all_suits() {
+        match self.stage {
+            GameStage::Done { .. } => StepOutcome::Done,
+            GameStage::AwaitingInput { who } => StepOutcome::AwaitingInput { who },
+            GameStage::Dealing { .. } | GameStage::Playing { .. } => StepOutcome::Continue,
+        }
+    }
+    /// Every ask `pid` could make with their current hand. Empty when they
+    /// have no cards or nobody is left to ask.
+    pub fn legal_actions(
+        &self,
+        pid: PlayerId,
+    ) -> Result<impl Iterator<Item = Action> + '_, GameError> {
+        let hand = self.player(pid)?.hand;
+        Ok(strategy::legal_actions(
+            pid,
+            hand,
+            self.players
+                .iter()
+                .enumerate()
+                .map(|(other, p)| (PlayerId(other as _), !p.hand.is_empty())),
+        ))
+    }
+    /// The player in seat `pid`, or an error if there is no such seat.
+    pub fn player(&self, pid: PlayerId) -> Result<&Player, GameError> {
+        self.players
+            .get(pid.0 as usize)
+            .ok_or(GameError::UnknownPlayer { player: pid })
+    }
+    fn check_action(&self, pid: PlayerId, action: Action) -> Result<(), GameError> {
+        self.player(pid)?;
+        self.player(action.ask_who)?;
+        if pid == action.ask_who {
+            return Err(GameError::AskedSelf { player: pid });
+        }
+        if !self.players[pid.0 as usize]
+            .hand
+            .ranks()
+            .contains(action.ask_for.into())
+        {
+            return Err(GameError::RankNotHeld {
+                player: pid,
+                rank: action.ask_for,
+            });
+        }
+        if self.players[action.ask_who.0 as usize].hand.is_empty() {
+            return Err(GameError::AskedEmptyHand {
+                player: pid,
+                asked: action.ask_who,
+            });
+        }
+        Ok(())
+    }
+    /// Moves the cards to where they lie on `table`, handing each player the
+    /// cards they gain. Books made on `table` stay in hand until
+    /// [`Game::record_books`].
+    fn move_cards(&mut self, table: &SimState) {
+        self.ctx.update(&self.players);
+        for (p, (&hand, &books)) in self
+            .players
+            .iter_mut()
+            .zip(table.hands.iter().zip(&table.books))
+        {
+            let hand = (books - p.books)
+                .iter()
+                .fold(hand, |cs, r| cs.union(r.in_all_suits()));
+            for c in p.hand.remove(hand).iter() {
+                p.take(c);
+            }
+            for c in hand.remove(p.hand).iter() {
+                p.deal_card(&self.ctx, c);
+            }
+        }
+        self.pool = table.pool;
+    }
+    /// Puts down the books made on `table`, announcing each of them.
+    fn record_books(&mut self, table: &SimState) {
+        for (pid, (p, &books)) in self.players.iter_mut().zip(&table.books).enumerate() {
+            let pid = PlayerId(pid as _);
+            for rank in (books - p.books).iter() {
                 p.hand = p.hand.remove(rank.in_all_suits());
-                for c in rank.in_all_suits().iter() {
-                    seen = seen.add(c);
-                }
                 self.announcements.push(Announcement::GotBook {
                     player: pid,
                     book: rank,
                 });
+                self.observers.book_completed(pid, rank);
                 p.books |= rank.into();
             }
         }
     }
-    fn step_inner(&mut self) {
+    fn step_inner(&mut self) -> Result<(), GameError> {
         match self.stage.clone() {
             GameStage::Dealing { who_next } => {
                 let p: &mut Player = &mut self.players[who_next.0 as usize];
@@ -171,134 +514,162 @@ impl Game {
                     self.stage = GameStage::Playing { who_next };
 
                     for i in 0..self.players.len() {
-                        assert_eq!(
-                            self.players[i].hand.num(),
-                            self.starting_cards,
-                            "Oh no! Player {} only has {} cards. There are {} left in the pool",
-                            i,
-                            self.players[i].hand.num(),
-                            self.pool.num()
-                        );
-
-                        self.check_books(PlayerId(i as _));
+                        if self.players[i].hand.num() != self.starting_cards {
+                            return Err(GameError::InvariantViolation(format!(
+                                "player {} only has {} cards. There are {} left in the pool",
+                                i,
+                                self.players[i].hand.num(),
+                                self.pool.num()
+                            )));
+                        }
                     }
+
+                    self.announcements.push(Announcement::DealComplete);
+
+                    let table = SimState::new(
+                        self.players.iter().map(|p| p.hand).collect(),
+                        self.pool,
+                        who_next,
+                        self.rules,
+                    );
+                    self.record_books(&table);
                 } else {
-                    let Some(c) = self.pool.choose_random() else {
-                        panic!("did not find any card");
+                    let Some(c) = self.pool.choose_random(&self.rng) else {
+                        return Err(GameError::InvariantViolation(
+                            "the pool ran dry while dealing".to_string(),
+                        ));
                     };
                     self.pool = self.pool.remove_one(c);
                     p.deal_card(&self.ctx, c);
+                    self.observers.card_dealt(who_next, c);
                     self.stage = GameStage::Dealing {
                         who_next: PlayerId((who_next.0 + 1) % self.players.len() as u32),
                     };
                 }
             }
             GameStage::Playing { who_next } => {
-                if self.players.iter().all(|p| p.hand.is_empty()) {
-                    self.stage = GameStage::Done { who_next };
-                    return;
+                if let Some(auto) = self.automatic_move(who_next) {
+                    self.play_automatic_move(who_next, auto);
+                    return Ok(());
                 }
 
-                if self.players.iter().filter(|p| !p.hand.is_empty()).count() == 1 {
-                    let (idx, p) = self
-                        .players
-                        .iter_mut()
-                        .enumerate()
-                        .find(|(_, p)| !p.hand.is_empty())
-                        .unwrap();
-                    p.hand = p.hand.union(self.pool);
-
-                    self.pool = Cards::empty();
-
-                    self.check_books(PlayerId(idx as _));
-
-                    return;
+                if self.players[who_next.0 as usize].strategy.is_external() {
+                    self.stage = GameStage::AwaitingInput { who: who_next };
+                    return Ok(());
                 }
 
                 self.ctx.update(&self.players);
-                let p: &mut Player = &mut self.players[who_next.0 as usize];
-
-                if p.hand.is_empty() {
-                    self.stage = GameStage::Playing {
-                        who_next: PlayerId((who_next.0 + 1) % self.players.len() as u32),
-                    };
-                    return;
-                }
-
-                if let Some(action) = p.strategy.action(&self.ctx) {
-                    if who_next == action.ask_who {
-                        panic!("Tried to ask self for cards!");
-                        return;
+                if self.players[who_next.0 as usize].strategy.sees_all_hands() {
+                    if !self.full_information {
+                        return Err(GameError::FullInformation { player: who_next });
                     }
-
-                    let rank = action.ask_for;
-
-                    if p.hand.intersection(rank.in_all_suits()).is_empty() {
-                        info!(
-                            "Asked for a card they didn't have! {:?} asked for {:?}",
-                            p, rank
-                        );
-                        return;
-                    }
-
-                    self.ctx.update(&self.players);
-                    let asked: &mut Player = &mut self.players[action.ask_who.0 as usize];
-                    let response = if asked.hand.intersection(rank.in_all_suits()).is_empty() {
-                        if let Some(drawn) = self.pool.choose_random() {
-                            self.pool = self.pool.remove_one(drawn);
-
-                            let p: &mut Player = &mut self.players[who_next.0 as usize];
-                            p.deal_card(&self.ctx, drawn);
-
-                            self.check_validity();
-
-                            Response::GoFish
-                        } else {
-                            self.check_validity();
-
-                            Response::GoFish
-                        }
-                    } else {
-                        let had = asked.hand.intersection(rank.in_all_suits());
-
-                        for c in had.iter() {
-                            asked.take(c);
-                        }
-                        let p: &mut Player = &mut self.players[who_next.0 as usize];
-                        for c in had.iter() {
-                            p.deal_card(&self.ctx, c);
-                        }
-
-                        Response::TakeThese { count: had.num() }
-                    };
-
-                    let announcement = Announcement::Action {
-                        player_asking: who_next,
-                        player_asked: action.ask_who,
-                        // asked_for: face,
-                        asked_for: rank,
-                        response,
-                    };
-
-                    self.announcements.push(announcement);
-
-                    self.stage = GameStage::Playing {
-                        who_next: PlayerId((who_next.0 + 1) % self.players.len() as u32),
-                    };
-
-                    self.check_books(who_next);
-                } else {
-                    info!("Player had no action...");
+                    self.ctx.reveal_hands(&self.players);
                 }
-
                 let p: &mut Player = &mut self.players[who_next.0 as usize];
-                if p.hand.is_empty() {
-                    self.stage = GameStage::Playing {
-                        who_next: PlayerId((who_next.0 + 1) % self.players.len() as u32),
-                    };
-                }
+                let action = p.strategy.action(&self.ctx);
+                self.ctx.conceal_hands();
+                let Some(action) = action else {
+                    return Err(GameError::NoAction { player: who_next });
+                };
+                self.check_action(who_next, action)?;
+
+                self.resolve_ask(who_next, action)?;
+            }
+            GameStage::AwaitingInput { .. } | GameStage::Done { .. } => {}
+        }
+
+        Ok(())
+    }
+    /// The move `who_next` is forced to make on their turn, if the rules leave
+    /// them no choice.
+    fn automatic_move(&self, who_next: PlayerId) -> Option<AutomaticMove> {
+        SimState::at_turn(self, who_next).automatic_move()
+    }
+    fn play_automatic_move(&mut self, who_next: PlayerId, auto: AutomaticMove) {
+        let mut table = SimState::at_turn(self, who_next);
+        let drawn = if table.draws(auto) {
+            table.pool.choose_random(&self.rng)
+        } else {
+            None
+        };
+        let announcement = match auto {
+            AutomaticMove::Finish => None,
+            AutomaticMove::ClaimPool(pid) => {
+                self.observers.pool_claimed(pid, self.pool);
+                Some(Announcement::PoolClaimed {
+                    player: pid,
+                    count: self.pool.num(),
+                })
             }
-            GameStage::Done { .. } => {}
+            AutomaticMove::DrawForEmptyHand => {
+                drawn.map(|_| Announcement::Drew { player: who_next })
+            }
+            AutomaticMove::Skip => Some(Announcement::TurnSkipped {
+                player: who_next,
+                reason: SkipReason::EmptyHand,
+            }),
+            AutomaticMove::FishAlone => Some(match drawn {
+                Some(_) => Announcement::Drew { player: who_next },
+                None => Announcement::TurnSkipped {
+                    player: who_next,
+                    reason: SkipReason::NobodyToAsk,
+                },
+            }),
+        };
+        table.play_automatic_move(auto, drawn);
+
+        self.move_cards(&table);
+        if let Some(drawn) = drawn {
+            self.observers.card_fished(who_next, drawn);
+        }
+        self.announcements.extend(announcement);
+        self.record_books(&table);
+
+        self.stage = if table.is_done() {
+            GameStage::Done {
+                who_next: table.who_next,
+            }
+        } else {
+            GameStage::Playing {
+                who_next: table.who_next,
+            }
+        };
+    }
+    /// Carries out an ask that has already been checked to be legal.
+    fn resolve_ask(&mut self, who_next: PlayerId, action: Action) -> Result<(), GameError> {
+        self.observers
+            .ask_made(who_next, action.ask_who, action.ask_for);
+
+        let mut table = SimState::at_turn(self, who_next);
+        let drawn = if table.fishes(action) {
+            table.pool.choose_random(&self.rng)
+        } else {
+            None
+        };
+        let response = table.ask(action, drawn);
+
+        self.move_cards(&table);
+        if let Some(drawn) = drawn {
+            self.observers.card_fished(who_next, drawn);
         }
+        if response == Response::GoFish {
+            self.check_validity()?;
+        }
+
+        self.observers
+            .response(who_next, action.ask_who, action.ask_for, response);
+        self.announcements.push(Announcement::Action {
+            player_asking: who_next,
+            player_asked: action.ask_who,
+            asked_for: action.ask_for,
+            response,
+        });
+        self.record_books(&table);
+
+        self.stage = GameStage::Playing {
+            who_next: table.who_next,
+        };
+
+        Ok(())
     }
 }
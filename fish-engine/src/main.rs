@@ -1,4 +1,9 @@
-use fish_engine::{strategy::Strat, Game};
+use std::collections::BTreeMap;
+
+use fish_engine::{
+    oracle_strat::Oracle, outcome::GameResult, registry::StrategyRegistry, strategy::StratBuilder,
+    Game, StepOutcome,
+};
 
 fn main() {
     env_logger::builder()
@@ -6,43 +11,137 @@ fn main() {
         .init();
     log::info!("Starting out...");
 
-    let mut counts = [0; 6];
+    // The simulator is not fair play, so the oracle may sit at the table.
+    StrategyRegistry::register_global::<Oracle>("oracle");
+
+    // With --ceiling, every game is replayed once per seat with the oracle in
+    // that seat, to see what fraction of the possible books each strategy gets.
+    let ceiling = std::env::args().any(|a| a == "--ceiling");
+    let mut args = std::env::args().skip(1).filter(|a| !a.starts_with("--"));
+    let num_players: usize = args
+        .next()
+        .map(|a| a.parse().expect("the number of players must be a number"))
+        .unwrap_or(6);
+    let starting_cards: u32 = args
+        .next()
+        .map(|a| {
+            a.parse()
+                .expect("the number of starting cards must be a number")
+        })
+        .unwrap_or(5);
+    let strategies: Vec<String> = args
+        .next()
+        .map(|a| a.split(',').map(str::to_string).collect())
+        .unwrap_or_else(|| vec!["random".to_string()]);
+
+    let mut counts = BTreeMap::<String, u32>::new();
+    let mut ties = 0;
+    // Seats played, books made, and books the oracle made in the same seats.
+    let mut books = BTreeMap::<String, (u32, u32, u32)>::new();
 
     let count = 10000;
     let before = std::time::Instant::now();
     for iter in 0..count {
-        let mut strats = [
-            Strat::random(),
-            Strat::random(),
-            Strat::random(),
-            Strat::random(),
-            Strat::random(),
-            Strat::random(),
-        ];
+        let mut strats = strategies
+            .iter()
+            .cycle()
+            .take(num_players)
+            .map(StratBuilder::new)
+            .collect::<Vec<_>>();
         fastrand::shuffle(&mut strats);
-        let mut game = Game::new(5, strats);
+        let mut game = match Game::new(starting_cards, strats).map(Game::allow_full_information) {
+            Ok(game) => game,
+            Err(err) => {
+                eprintln!("{err}");
+                std::process::exit(1);
+            }
+        };
 
-        while !game.stage.is_done() {
-            game.step();
+        let finished = loop {
+            match game.step() {
+                Ok(StepOutcome::Continue) => {}
+                Ok(StepOutcome::Done) => break true,
+                Ok(StepOutcome::AwaitingInput { who }) => {
+                    log::error!(
+                        "Abandoning game {}: player {} is played by a person",
+                        iter + 1,
+                        who.0
+                    );
+                    break false;
+                }
+                Err(err) => {
+                    log::error!("Abandoning game {}: {err}", iter + 1);
+                    break false;
+                }
+            }
+        };
+        if !finished {
+            continue;
         }
 
-        let (winner, _) = game
-            .players
-            .iter()
-            .enumerate()
-            .max_by_key(|(_, p)| p.books.iter().count())
-            .unwrap();
+        let outcome = game.outcome().expect("the game is done");
+
+        for (seat, p) in game.players.iter().enumerate() {
+            let (seats, made, possible) = books.entry(p.strategy.name().to_string()).or_default();
+            *seats += 1;
+            *made += outcome.scores[seat];
+            if ceiling {
+                *possible += oracle_books(&game, seat);
+            }
+        }
 
         eprintln!("{}/{count}", iter + 1);
-        // println!("{winner}");
 
-        counts[winner] += 1;
+        match outcome.result {
+            GameResult::Winner(winner) => {
+                let name = game.players[winner.0 as usize].strategy.name();
+                *counts.entry(name.to_string()).or_default() += 1;
+            }
+            GameResult::Tie(_) => ties += 1,
+        }
     }
 
-    eprintln!("{:?}", counts);
+    eprintln!("{:?} ({ties} ties)", counts);
+    if ceiling {
+        for (name, &(seats, made, possible)) in &books {
+            eprintln!(
+                "{name}: {:.2} books per seat, {:.1}% of the oracle's {:.2}",
+                made as f32 / seats as f32,
+                100.0 * made as f32 / possible as f32,
+                possible as f32 / seats as f32,
+            );
+        }
+    }
 
     eprintln!(
         "{}µs/sample",
         before.elapsed().as_micros() as f32 / count as f32
     );
 }
+
+/// The books the oracle makes in `seat` of `game`, with everything else kept
+/// the same.
+fn oracle_books(game: &Game, seat: usize) -> u32 {
+    let mut seats = game
+        .players
+        .iter()
+        .map(|p| p.strategy.builder().clone())
+        .collect::<Vec<_>>();
+    seats[seat] = StratBuilder::new("oracle");
+    let mut oracle = Game::with_seed(game.seed(), game.starting_cards(), seats)
+        .expect("the same seats worked before")
+        .with_rules(game.rules())
+        .allow_full_information();
+    loop {
+        match oracle.step() {
+            Ok(StepOutcome::Continue) => {}
+            Ok(StepOutcome::Done) => break,
+            Ok(StepOutcome::AwaitingInput { .. }) => return 0,
+            Err(err) => {
+                log::error!("Abandoning oracle game: {err}");
+                return 0;
+            }
+        }
+    }
+    oracle.players[seat].books.bits().count_ones()
+}
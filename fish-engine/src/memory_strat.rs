@@ -0,0 +1,82 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Card, Cards, Rank},
+    common_strat::update_hand_on_announcement,
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// Counts cards: asks whoever is known to hold a rank we have, and otherwise
+/// prefers ranks the table already knows we hold, so that asking gives away as
+/// little as possible.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Memory {
+    pid: PlayerId,
+    hand: Cards,
+}
+
+impl ConfigurableStrategy for Memory {
+    type Config = ();
+
+    fn init(pid: PlayerId, _config: ()) -> Self {
+        Memory {
+            pid,
+            hand: Cards::empty(),
+        }
+    }
+}
+
+impl Memory {
+    fn held(&self, rank: Rank) -> u32 {
+        self.hand.intersection(rank.in_all_suits()).num()
+    }
+}
+
+impl Strategy for Memory {
+    fn deal_card(&mut self, _ctx: &Context, card: Card) {
+        self.hand = self.hand.add(card);
+    }
+
+    fn action(&mut self, ctx: &Context) -> Option<Action> {
+        let knowledge = ctx.knowledge();
+        let opponents = ctx
+            .players()
+            .filter(|&(pid, p)| pid != self.pid && p.cards_on_hand > 0)
+            .map(|(pid, _)| pid)
+            .collect_vec();
+        let ranks = self.hand.ranks();
+
+        let sure_catch = ranks
+            .iter()
+            .cartesian_product(opponents.iter().copied())
+            .filter(|&(rank, who)| knowledge.at_least(who, rank) > 0)
+            .max_by_key(|&(rank, who)| knowledge.at_least(who, rank) + self.held(rank));
+        if let Some((ask_for, ask_who)) = sure_catch {
+            return Some(Action { ask_who, ask_for });
+        }
+
+        let ask_for = ranks
+            .iter()
+            .max_by_key(|&rank| (knowledge.at_least(self.pid, rank) > 0, self.held(rank)))?;
+        let might_have = opponents
+            .iter()
+            .copied()
+            .filter(|&who| !knowledge.has_none(who, ask_for))
+            .collect_vec();
+        let ask_who = *crate::pick(
+            ctx.rng(),
+            if might_have.is_empty() {
+                &opponents
+            } else {
+                &might_have
+            },
+        )?;
+
+        Some(Action { ask_who, ask_for })
+    }
+
+    fn react(&mut self, _ctx: &Context, ann: Announcement) {
+        update_hand_on_announcement(self.pid, ann, &mut self.hand);
+    }
+}
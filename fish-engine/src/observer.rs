@@ -0,0 +1,119 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+use crate::{
+    cards::{Card, Cards, Rank},
+    outcome::Outcome,
+    strategy::{PlayerId, Response},
+};
+
+/// Callbacks for everything that happens in a [`Game`](crate::Game). All of
+/// them do nothing by default, so an observer only implements what it needs.
+///
+/// Observers see every card that moves, so they should not be handed to
+/// strategies.
+pub trait GameObserver {
+    fn card_dealt(&mut self, _player: PlayerId, _card: Card) {}
+    fn ask_made(&mut self, _asking: PlayerId, _asked: PlayerId, _rank: Rank) {}
+    fn response(&mut self, _asking: PlayerId, _asked: PlayerId, _rank: Rank, _res: Response) {}
+    /// `player` drew `card` from the pool, either after being told to go fish
+    /// or because the rules had them draw.
+    fn card_fished(&mut self, _player: PlayerId, _card: Card) {}
+    /// The only player left with cards took what remained of the pool.
+    fn pool_claimed(&mut self, _player: PlayerId, _cards: Cards) {}
+    fn book_completed(&mut self, _player: PlayerId, _rank: Rank) {}
+    fn turn_passed(&mut self, _from: PlayerId, _to: PlayerId) {}
+    fn game_finished(&mut self, _outcome: &Outcome) {}
+}
+
+/// Lets the caller keep a handle to an observer after giving it to a game.
+impl<T: GameObserver> GameObserver for Rc<RefCell<T>> {
+    fn card_dealt(&mut self, player: PlayerId, card: Card) {
+        self.borrow_mut().card_dealt(player, card)
+    }
+    fn ask_made(&mut self, asking: PlayerId, asked: PlayerId, rank: Rank) {
+        self.borrow_mut().ask_made(asking, asked, rank)
+    }
+    fn response(&mut self, asking: PlayerId, asked: PlayerId, rank: Rank, res: Response) {
+        self.borrow_mut().response(asking, asked, rank, res)
+    }
+    fn card_fished(&mut self, player: PlayerId, card: Card) {
+        self.borrow_mut().card_fished(player, card)
+    }
+    fn pool_claimed(&mut self, player: PlayerId, cards: Cards) {
+        self.borrow_mut().pool_claimed(player, cards)
+    }
+    fn book_completed(&mut self, player: PlayerId, rank: Rank) {
+        self.borrow_mut().book_completed(player, rank)
+    }
+    fn turn_passed(&mut self, from: PlayerId, to: PlayerId) {
+        self.borrow_mut().turn_passed(from, to)
+    }
+    fn game_finished(&mut self, outcome: &Outcome) {
+        self.borrow_mut().game_finished(outcome)
+    }
+}
+
+/// The observers registered on a game, which are all notified in turn.
+#[derive(Default)]
+pub struct Observers(Vec<Box<dyn GameObserver>>);
+
+impl Observers {
+    pub fn push(&mut self, observer: Box<dyn GameObserver>) {
+        self.0.push(observer);
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+impl GameObserver for Observers {
+    fn card_dealt(&mut self, player: PlayerId, card: Card) {
+        for o in &mut self.0 {
+            o.card_dealt(player, card);
+        }
+    }
+    fn ask_made(&mut self, asking: PlayerId, asked: PlayerId, rank: Rank) {
+        for o in &mut self.0 {
+            o.ask_made(asking, asked, rank);
+        }
+    }
+    fn response(&mut self, asking: PlayerId, asked: PlayerId, rank: Rank, res: Response) {
+        for o in &mut self.0 {
+            o.response(asking, asked, rank, res);
+        }
+    }
+    fn card_fished(&mut self, player: PlayerId, card: Card) {
+        for o in &mut self.0 {
+            o.card_fished(player, card);
+        }
+    }
+    fn pool_claimed(&mut self, player: PlayerId, cards: Cards) {
+        for o in &mut self.0 {
+            o.pool_claimed(player, cards);
+        }
+    }
+    fn book_completed(&mut self, player: PlayerId, rank: Rank) {
+        for o in &mut self.0 {
+            o.book_completed(player, rank);
+        }
+    }
+    fn turn_passed(&mut self, from: PlayerId, to: PlayerId) {
+        for o in &mut self.0 {
+            o.turn_passed(from, to);
+        }
+    }
+    fn game_finished(&mut self, outcome: &Outcome) {
+        for o in &mut self.0 {
+            o.game_finished(outcome);
+        }
+    }
+}
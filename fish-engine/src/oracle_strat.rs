@@ -0,0 +1,79 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Card, Cards},
+    common_strat::update_hand_on_announcement,
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// Cheats by looking at every hand: asks whoever holds the most of a rank it
+/// has, and otherwise fishes for the rank most plentiful in the pool.
+///
+/// Not part of the [default registry](crate::registry::StrategyRegistry), and
+/// only plays in games that [allow full
+/// information](crate::Game::allow_full_information). The simulator uses it as
+/// the ceiling the fair strategies are measured against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Oracle {
+    pid: PlayerId,
+    hand: Cards,
+}
+
+impl ConfigurableStrategy for Oracle {
+    type Config = ();
+
+    fn init(pid: PlayerId, _config: ()) -> Self {
+        Oracle {
+            pid,
+            hand: Cards::empty(),
+        }
+    }
+}
+
+impl Strategy for Oracle {
+    fn deal_card(&mut self, _ctx: &Context, card: Card) {
+        self.hand = self.hand.add(card);
+    }
+
+    fn action(&mut self, ctx: &Context) -> Option<Action> {
+        let hands = ctx.hands()?;
+        let actions = ctx.legal_actions(self.pid, self.hand).collect_vec();
+
+        let catch = actions
+            .iter()
+            .map(|a| {
+                let held = hands[a.ask_who.0 as usize].intersection(a.ask_for.in_all_suits());
+                (*a, held.num())
+            })
+            .filter(|&(_, count)| count > 0)
+            .max_by_key(|&(_, count)| count);
+        if let Some((action, _)) = catch {
+            return Some(action);
+        }
+
+        let knowledge = ctx.knowledge();
+        let pool = hands
+            .iter()
+            .fold(knowledge.deck(), |pool, hand| pool.remove(*hand));
+        let ask_for = self
+            .hand
+            .ranks()
+            .iter()
+            .max_by_key(|r| pool.intersection(r.in_all_suits()).num())?;
+        let targets = actions
+            .iter()
+            .filter(|a| a.ask_for == ask_for)
+            .copied()
+            .collect_vec();
+        crate::pick(ctx.rng(), &targets).copied()
+    }
+
+    fn react(&mut self, _ctx: &Context, ann: Announcement) {
+        update_hand_on_announcement(self.pid, ann, &mut self.hand);
+    }
+
+    fn sees_all_hands(&self) -> bool {
+        true
+    }
+}
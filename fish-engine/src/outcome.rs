@@ -0,0 +1,119 @@
+use serde::Serialize;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+use crate::{
+    cards::{Rank, Ranks},
+    strategy::PlayerId,
+    Game,
+};
+
+/// Turns the books a player has made into a score.
+pub trait Scoring {
+    fn score(&self, books: Ranks) -> u32;
+}
+
+/// One point per book, regardless of rank.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BookCount;
+
+impl Scoring for BookCount {
+    fn score(&self, books: Ranks) -> u32 {
+        books.iter().count() as _
+    }
+}
+
+/// A fixed number of points for each rank, indexed by `Rank as usize`.
+#[derive(Debug, Clone, Copy)]
+pub struct RankPoints(pub [u32; 13]);
+
+impl RankPoints {
+    pub fn points(&self, rank: Rank) -> u32 {
+        self.0[rank as usize]
+    }
+}
+
+impl Scoring for RankPoints {
+    fn score(&self, books: Ranks) -> u32 {
+        books.iter().map(|r| self.points(r)).sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+#[serde(tag = "type", content = "content")]
+pub enum GameResult {
+    Winner(PlayerId),
+    /// Several players share the highest score.
+    Tie(Vec<PlayerId>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi))]
+pub struct Outcome {
+    /// The score of each player, indexed by seat.
+    pub scores: Vec<u32>,
+    /// Players grouped by score, best first. Players in the same group are
+    /// tied with each other.
+    pub standings: Vec<Vec<PlayerId>>,
+    pub result: GameResult,
+}
+
+impl Outcome {
+    pub fn new(scoring: &impl Scoring, books: impl IntoIterator<Item = Ranks>) -> Self {
+        let scores = books
+            .into_iter()
+            .map(|b| scoring.score(b))
+            .collect::<Vec<_>>();
+
+        let mut ranked = scores
+            .iter()
+            .enumerate()
+            .map(|(pid, &s)| (PlayerId(pid as _), s))
+            .collect::<Vec<_>>();
+        ranked.sort_by_key(|&(pid, s)| (std::cmp::Reverse(s), pid));
+
+        let mut standings: Vec<Vec<PlayerId>> = vec![];
+        let mut prev = None;
+        for (pid, s) in ranked {
+            match standings.last_mut() {
+                Some(group) if prev == Some(s) => group.push(pid),
+                _ => standings.push(vec![pid]),
+            }
+            prev = Some(s);
+        }
+
+        let result = match standings.first().map(Vec::as_slice) {
+            Some([winner]) => GameResult::Winner(*winner),
+            Some(tied) => GameResult::Tie(tied.to_vec()),
+            None => GameResult::Tie(vec![]),
+        };
+
+        Outcome {
+            scores,
+            standings,
+            result,
+        }
+    }
+    pub fn winners(&self) -> &[PlayerId] {
+        match &self.result {
+            GameResult::Winner(pid) => std::slice::from_ref(pid),
+            GameResult::Tie(pids) => pids,
+        }
+    }
+}
+
+impl Game {
+    /// Who won a finished game, counting one point per book. `None` while the
+    /// game is still being played.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome_with(&BookCount)
+    }
+    pub fn outcome_with(&self, scoring: &impl Scoring) -> Option<Outcome> {
+        if !self.stage.is_done() {
+            return None;
+        }
+
+        Some(Outcome::new(scoring, self.players.iter().map(|p| p.books)))
+    }
+}
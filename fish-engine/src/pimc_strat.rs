@@ -0,0 +1,92 @@
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Card, Cards},
+    common_strat::update_hand_on_announcement,
+    sim::{Rollout, SimState},
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
+};
+
+/// Perfect-information Monte Carlo: deals the unseen cards at random a number
+/// of times, plays every possible ask out in each deal as if all hands were
+/// face up, and makes the ask that gained the most books on average.
+///
+/// Stronger the more deals and playouts it is given, but it assumes it will
+/// know everything after this ask, so it never asks just to learn something.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pimc {
+    pid: PlayerId,
+    hand: Cards,
+    config: PimcConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PimcConfig {
+    /// How many deals to sample per decision.
+    pub deals: u32,
+    /// How many times to play each ask out in each deal.
+    pub playouts: u32,
+    /// How everyone asks in the playouts. [`Rollout::Greedy`] looks at the
+    /// dealt hands, which is what makes this perfect-information Monte Carlo;
+    /// with [`Rollout::Random`] it is plain Monte Carlo.
+    pub rollout: Rollout,
+}
+
+impl Default for PimcConfig {
+    fn default() -> Self {
+        PimcConfig {
+            deals: 20,
+            playouts: 4,
+            rollout: Rollout::Greedy,
+        }
+    }
+}
+
+impl ConfigurableStrategy for Pimc {
+    type Config = PimcConfig;
+
+    fn init(pid: PlayerId, config: PimcConfig) -> Self {
+        Pimc {
+            pid,
+            hand: Cards::empty(),
+            config,
+        }
+    }
+}
+
+impl Strategy for Pimc {
+    fn deal_card(&mut self, _ctx: &Context, card: Card) {
+        self.hand = self.hand.add(card);
+    }
+
+    fn action(&mut self, ctx: &Context) -> Option<Action> {
+        let rng = ctx.rng();
+        let actions = ctx.legal_actions(self.pid, self.hand).collect_vec();
+        let mut gains = vec![0; actions.len()];
+
+        for _ in 0..self.config.deals {
+            let deal = SimState::sample(ctx, self.pid, self.hand, rng);
+            let before = deal.book_counts()[self.pid.0 as usize];
+            for (&action, gain) in actions.iter().zip(&mut gains) {
+                for _ in 0..self.config.playouts {
+                    let mut state = deal.clone();
+                    state.apply(action, rng);
+                    state.play_out(self.config.rollout, rng);
+                    *gain += state.book_counts()[self.pid.0 as usize] - before;
+                }
+            }
+        }
+
+        actions
+            .iter()
+            .zip(&gains)
+            .max_by_key(|&(_, gain)| gain)
+            .map(|(&action, _)| action)
+    }
+
+    fn react(&mut self, _ctx: &Context, ann: Announcement) {
+        update_hand_on_announcement(self.pid, ann, &mut self.hand);
+    }
+}
@@ -1,26 +1,30 @@
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     cards::{Card, Cards},
     common_strat::update_hand_on_announcement,
-    strategy::{Action, Announcement, Context, PlayerId, Strategy},
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
 };
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Random {
     pid: PlayerId,
     hand: Cards,
 }
 
-impl Strategy for Random {
-    fn init(pid: PlayerId) -> Self {
+impl ConfigurableStrategy for Random {
+    type Config = ();
+
+    fn init(pid: PlayerId, _config: ()) -> Self {
         Random {
             pid,
             hand: Cards::empty(),
         }
     }
+}
 
+impl Strategy for Random {
     fn deal_card(&mut self, _ctx: &Context, card: Card) {
         self.hand = self.hand.add(card);
     }
@@ -44,13 +48,13 @@ impl Strategy for Random {
             );
         }
 
-        let ask_who = *crate::pick(&options)?;
+        let ask_who = *crate::pick(ctx.rng(), &options)?;
 
         // println!("{ask_who:?}");
 
         Some(Action {
             ask_who,
-            ask_for: self.hand.choose_random()?.rank(),
+            ask_for: self.hand.choose_random(ctx.rng())?.rank(),
         })
     }
 
@@ -0,0 +1,120 @@
+use std::{
+    collections::BTreeMap,
+    sync::{OnceLock, RwLock, RwLockReadGuard},
+};
+
+use serde_json::Value;
+
+use crate::{
+    belief_strat::Bayes,
+    error::GameError,
+    human_strat::Human,
+    ismcts_strat::Ismcts,
+    memory_strat::Memory,
+    pimc_strat::Pimc,
+    random_strat::Random,
+    strategy::{ConfigurableStrategy, DynStrategy, PlayerId, Strat, StratBuilder},
+    wiki_strat::Wiki,
+};
+
+type Build = fn(PlayerId, Value) -> Result<Box<dyn DynStrategy>, serde_json::Error>;
+type Restore = fn(Value) -> Result<Box<dyn DynStrategy>, serde_json::Error>;
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    build: Build,
+    restore: Restore,
+}
+
+/// Maps strategy names to the code that creates them, so that bots can live in
+/// other crates and be chosen by name at runtime.
+#[derive(Debug, Clone)]
+pub struct StrategyRegistry {
+    entries: BTreeMap<String, Entry>,
+}
+
+static GLOBAL: OnceLock<RwLock<StrategyRegistry>> = OnceLock::new();
+
+impl Default for StrategyRegistry {
+    /// A registry with the strategies that ship with the engine.
+    fn default() -> Self {
+        let mut registry = StrategyRegistry::empty();
+        registry.register::<Random>("random");
+        registry.register::<Wiki>("wiki");
+        registry.register::<Memory>("memory");
+        registry.register::<Bayes>("bayes");
+        registry.register::<Ismcts>("ismcts");
+        registry.register::<Pimc>("pimc");
+        registry.register::<Human>("human");
+        registry
+    }
+}
+
+impl StrategyRegistry {
+    pub fn empty() -> Self {
+        StrategyRegistry {
+            entries: BTreeMap::new(),
+        }
+    }
+    /// The registry [`Game`](crate::Game)s build their seats from and saved
+    /// games are restored through.
+    pub fn global() -> RwLockReadGuard<'static, StrategyRegistry> {
+        GLOBAL
+            .get_or_init(Default::default)
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+    /// Makes `T` available under `name` in the [global
+    /// registry](StrategyRegistry::global).
+    pub fn register_global<T: ConfigurableStrategy>(name: &str) {
+        GLOBAL
+            .get_or_init(Default::default)
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .register::<T>(name);
+    }
+    /// Makes `T` available under `name`, replacing whatever was there.
+    pub fn register<T: ConfigurableStrategy>(&mut self, name: &str) {
+        self.entries.insert(
+            name.to_string(),
+            Entry {
+                build: |pid, config| {
+                    let config = if config.is_null() {
+                        T::Config::default()
+                    } else {
+                        serde_json::from_value(config)?
+                    };
+                    Ok(Box::new(T::init(pid, config)))
+                },
+                restore: |state| Ok(Box::new(serde_json::from_value::<T>(state)?)),
+            },
+        );
+    }
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+    pub fn build(&self, builder: StratBuilder, pid: PlayerId) -> Result<Strat, GameError> {
+        let entry = self.entry(&builder.name)?;
+        let strategy = (entry.build)(pid, builder.config.clone()).map_err(|err| {
+            GameError::InvalidConfig(format!("bad config for {:?}: {err}", builder.name))
+        })?;
+        Ok(Strat::new(builder, strategy))
+    }
+    /// Recreates a strategy from the state it was saved with.
+    pub fn restore(&self, builder: StratBuilder, state: Value) -> Result<Strat, GameError> {
+        let entry = self.entry(&builder.name)?;
+        let strategy = (entry.restore)(state).map_err(|err| {
+            GameError::InvalidConfig(format!("bad state for {:?}: {err}", builder.name))
+        })?;
+        Ok(Strat::new(builder, strategy))
+    }
+    fn entry(&self, name: &str) -> Result<Entry, GameError> {
+        self.entries
+            .get(name)
+            .copied()
+            .ok_or_else(|| GameError::InvalidConfig(format!("unknown strategy {name:?}")))
+    }
+}
@@ -0,0 +1,287 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cards::{Cards, Rank},
+    error::GameError,
+    rules::{EndCondition, RuleSet},
+    strategy::{Action, Announcement, PlayerId, StratBuilder},
+    Game, GameStage,
+};
+
+const RANK_CHARS: &str = "A23456789TJQK";
+/// Full information is written along with the rules, in the bit after them.
+const FULL_INFORMATION_BIT: u8 = 4;
+
+/// Everything needed to play a game back exactly: how it was set up, and every
+/// ask that was made. The rest of the game follows from the seed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub starting_cards: u32,
+    pub rules: RuleSet,
+    pub deck: Cards,
+    /// Whether the game [allowed full
+    /// information](Game::allow_full_information).
+    #[serde(default)]
+    pub full_information: bool,
+    pub seats: Vec<StratBuilder>,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+#[display(fmt = "invalid replay code: {}", _0)]
+pub struct ParseReplayError(String);
+
+impl std::error::Error for ParseReplayError {}
+
+impl Replay {
+    /// Records the game played so far.
+    pub fn record(game: &Game) -> Replay {
+        Replay {
+            seed: game.seed(),
+            starting_cards: game.starting_cards(),
+            rules: game.rules(),
+            deck: game.deck(),
+            full_information: game.allows_full_information(),
+            seats: game
+                .players
+                .iter()
+                .map(|p| p.strategy.builder().clone())
+                .collect(),
+            actions: asks(&game.announcements).collect(),
+        }
+    }
+    /// Plays back every recorded action.
+    pub fn play(&self) -> Result<Game, GameError> {
+        self.play_to(usize::MAX)
+    }
+    /// Rebuilds the game as it was after `steps` calls to [`Game::step`] or
+    /// [`Game::submit_action`]. Stops early if the recorded actions run out.
+    ///
+    /// Seats played by strategies make their own asks, so that they end up in
+    /// the same state as in the recorded game, and it is an error if one of
+    /// them asks something other than what was recorded. Only the asks of
+    /// [external](crate::strategy::Strategy::is_external) seats are taken
+    /// from the recording.
+    pub fn play_to(&self, steps: usize) -> Result<Game, GameError> {
+        let mut game = Game::with_seed(self.seed, self.starting_cards, self.seats.iter().cloned())?
+            .with_rules(self.rules)
+            .with_deck(self.deck)?;
+        if self.full_information {
+            game = game.allow_full_information();
+        }
+        let mut actions = self.actions.iter().enumerate();
+
+        for _ in 0..steps {
+            if game.stage.is_done() {
+                break;
+            }
+            if let GameStage::AwaitingInput { who } = game.stage {
+                let Some((_, &action)) = actions.next() else {
+                    break;
+                };
+                game.submit_action(who, action)?;
+                continue;
+            }
+            let Some(who) = game.awaits_action() else {
+                game.step()?;
+                continue;
+            };
+            if game.players[who.0 as usize].strategy.is_external() {
+                game.step()?;
+                continue;
+            }
+
+            let Some((step, &expected)) = actions.next() else {
+                break;
+            };
+            let prev = game.announcements.len();
+            game.step()?;
+            if let Some(played) = asks(&game.announcements[prev..]).next() {
+                if played != expected {
+                    return Err(GameError::ReplayDiverged {
+                        step,
+                        expected,
+                        played,
+                    });
+                }
+            }
+        }
+
+        Ok(game)
+    }
+    /// A short, URL-safe encoding of the replay. Seats are written by name,
+    /// followed by `~` and their configuration as hex encoded JSON if they
+    /// have one. A reduced deck is appended as a sixth part.
+    ///
+    /// Each ask is the asked seat in base 36 followed by the rank. Games with
+    /// more than 36 seats use two digits for the seat.
+    pub fn to_code(&self) -> String {
+        let width = seat_width(self.seats.len());
+        let seats = self
+            .seats
+            .iter()
+            .map(seat_to_code)
+            .collect::<Vec<_>>()
+            .join("-");
+        let actions = self
+            .actions
+            .iter()
+            .flat_map(|a| {
+                let seat = (0..width as u32).rev().map(move |i| {
+                    char::from_digit(a.ask_who.0 / 36u32.pow(i) % 36, 36).expect("below 36")
+                });
+                seat.chain([RANK_CHARS.as_bytes()[a.ask_for as usize] as char])
+            })
+            .collect::<String>();
+
+        let mut code = format!(
+            "{:x}.{}.{:x}.{seats}.{actions}",
+            self.seed,
+            self.starting_cards,
+            rules_to_bits(self.rules) | (self.full_information as u8) << FULL_INFORMATION_BIT
+        );
+        if self.deck != Cards::all() {
+            code += &format!(".{:x}", self.deck.bits());
+        }
+        code
+    }
+    pub fn from_code(code: &str) -> Result<Replay, ParseReplayError> {
+        let err = |msg: &str| ParseReplayError(msg.to_string());
+
+        let parts = code.split('.').collect::<Vec<_>>();
+        let (parts, deck) = parts.split_at(parts.len().min(5));
+        let [seed, starting_cards, rules, seats, actions] = parts[..] else {
+            return Err(err("expected five parts separated by '.'"));
+        };
+        let deck = match deck {
+            [] => Cards::all(),
+            [deck] => u64::from_str_radix(deck, 16)
+                .ok()
+                .and_then(Cards::from_bits)
+                .ok_or_else(|| err("bad deck"))?,
+            _ => return Err(err("too many parts")),
+        };
+
+        let seed = u64::from_str_radix(seed, 16).map_err(|_| err("bad seed"))?;
+        let starting_cards = starting_cards
+            .parse()
+            .map_err(|_| err("bad number of starting cards"))?;
+        let bits = u8::from_str_radix(rules, 16).map_err(|_| err("bad rules"))?;
+        let full_information = bits & (1 << FULL_INFORMATION_BIT) != 0;
+        let rules =
+            rules_from_bits(bits & !(1 << FULL_INFORMATION_BIT)).ok_or_else(|| err("bad rules"))?;
+        let seats = seats
+            .split('-')
+            .map(|seat| seat_from_code(seat).ok_or_else(|| err("bad seat configuration")))
+            .collect::<Result<Vec<_>, _>>()?;
+        let width = seat_width(seats.len());
+
+        let chars = actions.chars().collect::<Vec<_>>();
+        if chars.len() % (width + 1) != 0 {
+            return Err(err("actions must be a seat followed by a rank"));
+        }
+        let actions = chars
+            .chunks(width + 1)
+            .map(|ask| {
+                let (seat, rank) = ask.split_at(width);
+                let ask_who = seat
+                    .iter()
+                    .try_fold(0, |n, c| Some(n * 36 + c.to_digit(36)?))
+                    .filter(|&n| (n as usize) < seats.len())
+                    .ok_or_else(|| err("bad player"))?;
+                let ask_for = RANK_CHARS
+                    .find(rank[0])
+                    .and_then(|r| Rank::from_repr(r as _))
+                    .ok_or_else(|| err("bad rank"))?;
+                Ok(Action {
+                    ask_who: PlayerId(ask_who),
+                    ask_for,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Replay {
+            seed,
+            starting_cards,
+            rules,
+            deck,
+            full_information,
+            seats,
+            actions,
+        })
+    }
+}
+
+/// The asks made in `announcements`, in order.
+fn asks(announcements: &[Announcement]) -> impl Iterator<Item = Action> + '_ {
+    announcements.iter().filter_map(|a| match *a {
+        Announcement::Action {
+            player_asked,
+            asked_for,
+            ..
+        } => Some(Action {
+            ask_who: player_asked,
+            ask_for: asked_for,
+        }),
+        _ => None,
+    })
+}
+
+fn seat_to_code(seat: &StratBuilder) -> String {
+    if seat.config.is_null() {
+        return seat.name().to_string();
+    }
+    let config = serde_json::to_vec(&seat.config).expect("JSON values always serialize");
+    let hex = config
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("{}~{hex}", seat.name())
+}
+
+fn seat_from_code(seat: &str) -> Option<StratBuilder> {
+    let Some((name, hex)) = seat.split_once('~') else {
+        return Some(StratBuilder::new(seat));
+    };
+    let config = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    let config = serde_json::from_slice(&config).ok()?;
+    Some(StratBuilder::new(name).with_config(config))
+}
+
+/// How many base 36 digits it takes to write the seat of any of `seats`.
+fn seat_width(seats: usize) -> usize {
+    if seats <= 36 {
+        1
+    } else {
+        2
+    }
+}
+
+fn rules_to_bits(rules: RuleSet) -> u8 {
+    (rules.another_turn_on_catch as u8)
+        | (rules.another_turn_on_lucky_fish as u8) << 1
+        | (rules.draw_when_empty as u8) << 2
+        | ((rules.end_condition == EndCondition::AllBooks) as u8) << 3
+}
+
+fn rules_from_bits(bits: u8) -> Option<RuleSet> {
+    if bits >> 4 != 0 {
+        return None;
+    }
+
+    Some(RuleSet {
+        another_turn_on_catch: bits & 1 != 0,
+        another_turn_on_lucky_fish: bits & (1 << 1) != 0,
+        draw_when_empty: bits & (1 << 2) != 0,
+        end_condition: if bits & (1 << 3) != 0 {
+            EndCondition::AllBooks
+        } else {
+            EndCondition::LastHandTakesPool
+        },
+    })
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+/// The toggles that distinguish the Go Fish variants the engine can play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+pub struct RuleSet {
+    /// Keep the turn after the asked player hands over cards.
+    pub another_turn_on_catch: bool,
+    /// Keep the turn when the fished card is of the rank that was asked for.
+    pub another_turn_on_lucky_fish: bool,
+    /// Draw from the pool at the start of your turn when your hand is empty,
+    /// instead of being skipped.
+    pub draw_when_empty: bool,
+    pub end_condition: EndCondition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+#[serde(tag = "type")]
+pub enum EndCondition {
+    /// As soon as only one player has cards left, they take the whole pool
+    /// and the game ends.
+    LastHandTakesPool,
+    /// Play continues until every book has been made. A player with nobody
+    /// left to ask fishes from the pool instead.
+    ///
+    /// Unless [`RuleSet::draw_when_empty`] is on, nobody can take cards from
+    /// the pool once every hand is empty, so the game ends there and the
+    /// books still in the pool are made by nobody.
+    AllBooks,
+}
+
+impl RuleSet {
+    /// The rules this engine has always played by.
+    pub fn house() -> Self {
+        RuleSet {
+            another_turn_on_catch: false,
+            another_turn_on_lucky_fish: false,
+            draw_when_empty: false,
+            end_condition: EndCondition::LastHandTakesPool,
+        }
+    }
+    /// The rules as commonly published for Go Fish.
+    pub fn standard() -> Self {
+        RuleSet {
+            another_turn_on_catch: true,
+            another_turn_on_lucky_fish: true,
+            draw_when_empty: true,
+            end_condition: EndCondition::AllBooks,
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet::house()
+    }
+}
@@ -0,0 +1,400 @@
+use fastrand::Rng;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{
+    cards::{Card, Cards, Rank, Ranks},
+    rules::{EndCondition, RuleSet},
+    strategy::{legal_actions, Action, Context, PlayerId, Response},
+    Game,
+};
+
+/// How many times [`SimState::sample`] tries to deal hands that agree with
+/// everything that is publicly known before it settles for one that does not.
+const SAMPLE_ATTEMPTS: usize = 32;
+
+/// A turn the rules play out without asking anyone for a decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AutomaticMove {
+    Finish,
+    /// The only player left with cards takes what remains in the pool.
+    ClaimPool(PlayerId),
+    DrawForEmptyHand,
+    Skip,
+    /// Nobody is left to ask, so the player fishes without asking.
+    FishAlone,
+}
+
+/// How the players in a simulated game choose their asks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Rollout {
+    /// Ask anyone for anything.
+    #[default]
+    Random,
+    /// Ask whoever holds the most of a rank we have, peeking at their hands,
+    /// and otherwise ask at random.
+    Greedy,
+}
+
+/// The bare table of a game in progress: hands, books and the pool, without
+/// strategies, announcements or observers. Cheap to clone, so that search
+/// based strategies can play thousands of games forward from it.
+///
+/// This is where the rules live: [`Game`] carries out its asks and the turns
+/// the rules play out automatically on a `SimState` of its own table, so a
+/// simulated game goes exactly like a real one. After [`SimState::apply`] it
+/// is always either [done](SimState::is_done) or waiting on
+/// [`SimState::who_next`] to ask.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimState {
+    pub hands: Vec<Cards>,
+    pub books: Vec<Ranks>,
+    pub pool: Cards,
+    pub who_next: PlayerId,
+    pub rules: RuleSet,
+    done: bool,
+}
+
+impl SimState {
+    /// A freshly dealt table, with any books in the hands already put down.
+    /// Automatic turns are not played until [`SimState::advance`].
+    pub fn new(hands: Vec<Cards>, pool: Cards, who_next: PlayerId, rules: RuleSet) -> Self {
+        let mut state = SimState {
+            books: vec![Ranks::empty(); hands.len()],
+            hands,
+            pool,
+            who_next,
+            rules,
+            done: false,
+        };
+        for pid in 0..state.hands.len() {
+            state.check_books(PlayerId(pid as _));
+        }
+        state
+    }
+
+    /// The true state of `game`, or `None` if it is not being played.
+    ///
+    /// This sees every hand, so strategies should only use it for positions
+    /// they are entitled to know everything about.
+    pub fn from_game(game: &Game) -> Option<Self> {
+        Some(SimState::at_turn(game, game.stage.whose_turn()?))
+    }
+
+    /// The true state of `game` with `who_next` to ask, whatever stage the
+    /// game is in.
+    pub(crate) fn at_turn(game: &Game, who_next: PlayerId) -> Self {
+        SimState {
+            hands: game.players.iter().map(|p| p.hand).collect(),
+            books: game.players.iter().map(|p| p.books).collect(),
+            pool: game.pool,
+            who_next,
+            rules: game.rules(),
+            done: false,
+        }
+    }
+
+    /// Deals out the cards `me` cannot see at random, in a way that agrees
+    /// with the hand sizes and with what `ctx` knows about every hand.
+    ///
+    /// Known cards are placed first and the rest are dealt one at a time to
+    /// the opponents that could be holding them, so the deals are only
+    /// roughly uniform. If no consistent deal turns up after a few attempts,
+    /// the last one ignores what is known about who holds what.
+    pub fn sample(ctx: &Context, me: PlayerId, hand: Cards, rng: &Rng) -> Self {
+        let knowledge = ctx.knowledge();
+        let booked = knowledge
+            .booked()
+            .iter()
+            .fold(Cards::empty(), |cs, r| cs.union(r.in_all_suits()));
+        let unseen = knowledge.deck().remove(hand).remove(booked);
+        let sizes = ctx
+            .players()
+            .map(|(_, p)| p.cards_on_hand as u32)
+            .collect_vec();
+
+        let mut hands = vec![Cards::empty(); sizes.len()];
+        let mut rest = unseen;
+        for attempt in 1..=SAMPLE_ATTEMPTS {
+            let strict = attempt < SAMPLE_ATTEMPTS;
+            hands.iter_mut().for_each(|h| *h = Cards::empty());
+            hands[me.0 as usize] = hand;
+            rest = unseen;
+
+            if Self::deal_unseen(ctx, me, &sizes, &mut hands, &mut rest, strict, rng) {
+                break;
+            }
+        }
+
+        SimState {
+            hands,
+            books: ctx.players().map(|(_, p)| p.books).collect(),
+            pool: rest,
+            who_next: me,
+            rules: ctx.rules(),
+            done: false,
+        }
+    }
+
+    fn deal_unseen(
+        ctx: &Context,
+        me: PlayerId,
+        sizes: &[u32],
+        hands: &mut [Cards],
+        rest: &mut Cards,
+        strict: bool,
+        rng: &Rng,
+    ) -> bool {
+        let knowledge = ctx.knowledge();
+        let mut opponents = (0..hands.len())
+            .map(|i| PlayerId(i as _))
+            .filter(|&pid| pid != me)
+            .collect_vec();
+        rng.shuffle(&mut opponents);
+
+        if strict {
+            for &pid in &opponents {
+                for rank in Rank::iter() {
+                    for _ in 0..knowledge.at_least(pid, rank) {
+                        let Some(c) = rest.intersection(rank.in_all_suits()).choose_random(rng)
+                        else {
+                            return false;
+                        };
+                        *rest = rest.remove_one(c);
+                        hands[pid.0 as usize] = hands[pid.0 as usize].add(c);
+                    }
+                }
+            }
+        }
+
+        for &pid in &opponents {
+            let i = pid.0 as usize;
+            while hands[i].num() < sizes[i] {
+                let could_hold = if strict {
+                    Rank::iter()
+                        .filter(|&r| {
+                            !knowledge.has_none(pid, r)
+                                && hands[i].intersection(r.in_all_suits()).num() < 3
+                        })
+                        .fold(Cards::empty(), |cs, r| cs.union(r.in_all_suits()))
+                } else {
+                    Cards::all()
+                };
+                let Some(c) = rest.intersection(could_hold).choose_random(rng) else {
+                    return false;
+                };
+                *rest = rest.remove_one(c);
+                hands[i] = hands[i].add(c);
+            }
+        }
+
+        true
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Every ask `pid` could make with their current hand.
+    pub fn legal_actions(&self, pid: PlayerId) -> impl Iterator<Item = Action> + '_ {
+        legal_actions(
+            pid,
+            self.hands[pid.0 as usize],
+            self.hands
+                .iter()
+                .enumerate()
+                .map(|(i, h)| (PlayerId(i as _), !h.is_empty())),
+        )
+    }
+
+    /// How many books each player has made.
+    pub fn book_counts(&self) -> Vec<u32> {
+        self.books.iter().map(|b| b.bits().count_ones()).collect()
+    }
+
+    /// Lets [`SimState::who_next`] ask, then plays out any turns that follow
+    /// automatically. Assumes `action` is legal.
+    pub fn apply(&mut self, action: Action, rng: &Rng) {
+        let drawn = if self.fishes(action) {
+            self.pool.choose_random(rng)
+        } else {
+            None
+        };
+        self.ask(action, drawn);
+        self.advance(rng);
+    }
+
+    /// Whether `action` would have [`SimState::who_next`] draw from the pool.
+    pub(crate) fn fishes(&self, action: Action) -> bool {
+        let asked = self.hands[action.ask_who.0 as usize];
+        asked.intersection(action.ask_for.in_all_suits()).is_empty() && !self.pool.is_empty()
+    }
+
+    /// Carries out an ask, drawing `drawn` if the asked player has nothing to
+    /// hand over, and returns the answer given.
+    pub(crate) fn ask(&mut self, action: Action, drawn: Option<Card>) -> Response {
+        let who = self.who_next;
+        let rank = action.ask_for;
+        let asked = &mut self.hands[action.ask_who.0 as usize];
+        let had = asked.intersection(rank.in_all_suits());
+
+        let (keeps_turn, response) = if had.is_empty() {
+            let keeps_turn = match drawn {
+                Some(drawn) => {
+                    self.pool = self.pool.remove_one(drawn);
+                    self.hands[who.0 as usize] = self.hands[who.0 as usize].add(drawn);
+                    self.rules.another_turn_on_lucky_fish && drawn.rank() == rank
+                }
+                None => false,
+            };
+            (keeps_turn, Response::GoFish)
+        } else {
+            *asked = asked.remove(had);
+            self.hands[who.0 as usize] = self.hands[who.0 as usize].union(had);
+            (
+                self.rules.another_turn_on_catch,
+                Response::TakeThese { count: had.num() },
+            )
+        };
+
+        self.check_books(who);
+        if !keeps_turn || self.hands[who.0 as usize].is_empty() {
+            self.who_next = self.next_player(who);
+        }
+        response
+    }
+
+    /// Plays the game to the end, with everyone asking according to
+    /// `rollout`.
+    pub fn play_out(&mut self, rollout: Rollout, rng: &Rng) {
+        self.advance(rng);
+        while !self.done {
+            let Some(action) = self.rollout_action(rollout, rng) else {
+                break;
+            };
+            self.apply(action, rng);
+        }
+    }
+
+    fn rollout_action(&self, rollout: Rollout, rng: &Rng) -> Option<Action> {
+        let actions = self.legal_actions(self.who_next).collect_vec();
+        if rollout == Rollout::Greedy {
+            let catch = actions
+                .iter()
+                .map(|a| {
+                    let held = self.hands[a.ask_who.0 as usize];
+                    (a, held.intersection(a.ask_for.in_all_suits()).num())
+                })
+                .filter(|&(_, count)| count > 0)
+                .max_by_key(|&(_, count)| count);
+            if let Some((&action, _)) = catch {
+                return Some(action);
+            }
+        }
+        crate::pick(rng, &actions).copied()
+    }
+
+    /// Plays the turns nobody has a say in until someone has to ask or the
+    /// game is over.
+    pub fn advance(&mut self, rng: &Rng) {
+        while let Some(auto) = self.automatic_move() {
+            let drawn = if self.draws(auto) {
+                self.pool.choose_random(rng)
+            } else {
+                None
+            };
+            self.play_automatic_move(auto, drawn);
+        }
+    }
+
+    /// The move [`SimState::who_next`] is forced to make on their turn, if the
+    /// rules leave them no choice.
+    pub(crate) fn automatic_move(&self) -> Option<AutomaticMove> {
+        if self.done {
+            return None;
+        }
+        // Without drawing into empty hands, what is left in the pool stays
+        // there. See `EndCondition::AllBooks`.
+        if self.hands.iter().all(|h| h.is_empty())
+            && (self.pool.is_empty() || !self.rules.draw_when_empty)
+        {
+            return Some(AutomaticMove::Finish);
+        }
+
+        let mut holding = self.hands.iter().positions(|h| !h.is_empty());
+        if let (Some(pid), None) = (holding.next(), holding.next()) {
+            if self.rules.end_condition == EndCondition::LastHandTakesPool {
+                return Some(AutomaticMove::ClaimPool(PlayerId(pid as _)));
+            }
+        }
+
+        let who = self.who_next;
+        if self.hands[who.0 as usize].is_empty() {
+            if self.rules.draw_when_empty && !self.pool.is_empty() {
+                return Some(AutomaticMove::DrawForEmptyHand);
+            }
+            return Some(AutomaticMove::Skip);
+        }
+
+        if self.legal_actions(who).next().is_none() {
+            return Some(AutomaticMove::FishAlone);
+        }
+
+        None
+    }
+
+    /// Whether `auto` has [`SimState::who_next`] draw from the pool.
+    pub(crate) fn draws(&self, auto: AutomaticMove) -> bool {
+        match auto {
+            AutomaticMove::DrawForEmptyHand | AutomaticMove::FishAlone => !self.pool.is_empty(),
+            AutomaticMove::Finish | AutomaticMove::ClaimPool(_) | AutomaticMove::Skip => false,
+        }
+    }
+
+    /// Plays `auto`, drawing `drawn` if it [draws](SimState::draws).
+    pub(crate) fn play_automatic_move(&mut self, auto: AutomaticMove, drawn: Option<Card>) {
+        let who = self.who_next;
+        match auto {
+            AutomaticMove::Finish => self.done = true,
+            AutomaticMove::ClaimPool(pid) => {
+                self.hands[pid.0 as usize] = self.hands[pid.0 as usize].union(self.pool);
+                self.pool = Cards::empty();
+                self.check_books(pid);
+            }
+            AutomaticMove::DrawForEmptyHand => {
+                self.draw(who, drawn);
+            }
+            AutomaticMove::Skip => self.who_next = self.next_player(who),
+            AutomaticMove::FishAlone => {
+                if self.draw(who, drawn) {
+                    self.check_books(who);
+                }
+                self.who_next = self.next_player(who);
+            }
+        }
+    }
+
+    fn draw(&mut self, pid: PlayerId, drawn: Option<Card>) -> bool {
+        let Some(drawn) = drawn else {
+            return false;
+        };
+        self.pool = self.pool.remove_one(drawn);
+        self.hands[pid.0 as usize] = self.hands[pid.0 as usize].add(drawn);
+        true
+    }
+
+    fn check_books(&mut self, pid: PlayerId) {
+        let hand = &mut self.hands[pid.0 as usize];
+        for rank in hand.ranks().iter() {
+            if hand.intersection(rank.in_all_suits()) == rank.in_all_suits() {
+                *hand = hand.remove(rank.in_all_suits());
+                self.books[pid.0 as usize] |= rank.into();
+            }
+        }
+    }
+
+    fn next_player(&self, pid: PlayerId) -> PlayerId {
+        PlayerId((pid.0 + 1) % self.hands.len() as u32)
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    cards::{Cards, Rank},
+    knowledge::PublicKnowledge,
+    rules::RuleSet,
+    sim::SimState,
+    strategy::{Action, PlayerId},
+};
+
+/// Where the cards are and whose turn it is. Books are left out, since they
+/// have no say in how the rest of the game goes.
+type Position = (u64, u64, u64, PlayerId);
+
+/// Solves two player games on small decks, such as those made with
+/// [`Cards::all_bounded`], with both hands face up.
+///
+/// Values are the books player 0 goes on to make less those player 1 goes on
+/// to make, averaged over the draws from the pool, with both players asking
+/// to get ahead. Since the solver sees every hand, these are the values of a
+/// different game from the one the strategies play, and not the best a fair
+/// player can do. [`FaceUpSolver::info_set_action_values`] averages them over
+/// the deals a player cannot tell apart. That makes a yardstick for comparing
+/// strategies, not an exact solution: it lets the player ask differently in
+/// deals they could not actually tell apart, so it is optimistic.
+///
+/// Every method panics unless the game has exactly two players.
+#[derive(Debug)]
+pub struct FaceUpSolver {
+    rules: RuleSet,
+    memo: HashMap<Position, f64>,
+}
+
+impl FaceUpSolver {
+    pub fn new(rules: RuleSet) -> Self {
+        FaceUpSolver {
+            rules,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// How many positions have been solved so far.
+    pub fn positions(&self) -> usize {
+        self.memo.len()
+    }
+
+    /// The value of `state` for player 0.
+    pub fn value(&mut self, state: &SimState) -> f64 {
+        self.check(state);
+        self.settle(state.clone())
+    }
+
+    /// The value of every ask [`SimState::who_next`] can make, from their
+    /// point of view. Empty unless someone has to ask.
+    pub fn action_values(&mut self, state: &SimState) -> Vec<(Action, f64)> {
+        self.check(state);
+        if state.automatic_move().is_some() || state.is_done() {
+            return vec![];
+        }
+        let sign = FaceUpSolver::sign(state.who_next);
+        state
+            .legal_actions(state.who_next)
+            .collect_vec()
+            .into_iter()
+            .map(|action| (action, sign * self.ask_value(state, action)))
+            .collect()
+    }
+
+    pub fn best_action(&mut self, state: &SimState) -> Option<Action> {
+        FaceUpSolver::best(self.action_values(state))
+    }
+
+    /// How much worse `action` is than the best ask, in books, for the player
+    /// making it.
+    pub fn regret(&mut self, state: &SimState, action: Action) -> f64 {
+        FaceUpSolver::regret_among(self.action_values(state), action)
+    }
+
+    fn regret_among(values: Vec<(Action, f64)>, action: Action) -> f64 {
+        let best = values.iter().map(|&(_, v)| v).fold(f64::MIN, f64::max);
+        let value = values
+            .iter()
+            .find(|&&(a, _)| a == action)
+            .map_or(f64::MIN, |&(_, v)| v);
+        best - value
+    }
+
+    /// Every deal `me` cannot tell apart from `state` by their own hand, the
+    /// books, the size of the other hand and what `knowledge` has gathered
+    /// about it from earlier asks.
+    pub fn info_set(state: &SimState, me: PlayerId, knowledge: &PublicKnowledge) -> Vec<SimState> {
+        assert_eq!(state.hands.len(), 2, "the solver only plays two players");
+        let other = 1 - me.0 as usize;
+        let unseen = state.hands[other].union(state.pool);
+
+        unseen
+            .iter()
+            .combinations(state.hands[other].num() as usize)
+            .map(|hand| hand.into_iter().fold(Cards::empty(), Cards::add))
+            // A whole rank in hand would already have been put down as a book.
+            .filter(|hand| {
+                hand.ranks()
+                    .iter()
+                    .all(|r| hand.intersection(r.in_all_suits()) != r.in_all_suits())
+            })
+            .filter(|hand| {
+                Rank::iter().all(|r| {
+                    let held = hand.intersection(r.in_all_suits()).num();
+                    held >= knowledge.at_least(PlayerId(other as _), r)
+                        && (held == 0 || !knowledge.has_none(PlayerId(other as _), r))
+                })
+            })
+            .map(|hand| {
+                let mut deal = state.clone();
+                deal.hands[other] = hand;
+                deal.pool = unseen.remove(hand);
+                deal
+            })
+            .collect()
+    }
+
+    /// The value of every ask, averaged over `deals`, which should all be
+    /// waiting on the same player with the same hand.
+    pub fn info_set_action_values(&mut self, deals: &[SimState]) -> Vec<(Action, f64)> {
+        let mut totals = Vec::<(Action, f64)>::new();
+        for deal in deals {
+            for (action, value) in self.action_values(deal) {
+                match totals.iter_mut().find(|(a, _)| *a == action) {
+                    Some((_, total)) => *total += value,
+                    None => totals.push((action, value)),
+                }
+            }
+        }
+        for (_, total) in &mut totals {
+            *total /= deals.len() as f64;
+        }
+        totals
+    }
+
+    /// How much worse `action` is than the best ask, on average over `deals`,
+    /// for the player making it.
+    pub fn info_set_regret(&mut self, deals: &[SimState], action: Action) -> f64 {
+        FaceUpSolver::regret_among(self.info_set_action_values(deals), action)
+    }
+
+    pub fn best_info_set_action(&mut self, deals: &[SimState]) -> Option<Action> {
+        FaceUpSolver::best(self.info_set_action_values(deals))
+    }
+
+    /// The value for player 0 of dealing `starting_cards` to both players
+    /// from `deck`, averaged over every possible deal, with player 0 asking
+    /// first.
+    pub fn deal_value(&mut self, deck: Cards, starting_cards: u32) -> f64 {
+        let mut total = 0.0;
+        let mut deals = 0;
+        for first in deck.iter().combinations(starting_cards as usize) {
+            let first = first.into_iter().fold(Cards::empty(), Cards::add);
+            let rest = deck.remove(first);
+            for second in rest.iter().combinations(starting_cards as usize) {
+                let second = second.into_iter().fold(Cards::empty(), Cards::add);
+                let state = SimState::new(
+                    vec![first, second],
+                    rest.remove(second),
+                    PlayerId(0),
+                    self.rules,
+                );
+                total += FaceUpSolver::lead(&state) + self.settle(state);
+                deals += 1;
+            }
+        }
+        total / deals as f64
+    }
+
+    fn check(&self, state: &SimState) {
+        assert_eq!(state.hands.len(), 2, "the solver only plays two players");
+        assert_eq!(
+            state.rules, self.rules,
+            "the solver was made for other rules"
+        );
+    }
+
+    fn best(values: Vec<(Action, f64)>) -> Option<Action> {
+        values
+            .into_iter()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(action, _)| action)
+    }
+
+    fn sign(pid: PlayerId) -> f64 {
+        if pid.0 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    /// How many more books player 0 has than player 1.
+    fn lead(state: &SimState) -> f64 {
+        let books = state.book_counts();
+        books[0] as f64 - books[1] as f64
+    }
+
+    /// The value of `state` once the turns nobody has a say in are played.
+    fn settle(&mut self, state: SimState) -> f64 {
+        let Some(auto) = state.automatic_move() else {
+            return if state.is_done() {
+                0.0
+            } else {
+                self.decide(&state)
+            };
+        };
+
+        let draws = if state.draws(auto) {
+            state.pool.iter().map(Some).collect_vec()
+        } else {
+            vec![None]
+        };
+        let outcomes = draws.len() as f64;
+        draws
+            .into_iter()
+            .map(|drawn| {
+                let mut next = state.clone();
+                next.play_automatic_move(auto, drawn);
+                FaceUpSolver::lead(&next) - FaceUpSolver::lead(&state) + self.settle(next)
+            })
+            .sum::<f64>()
+            / outcomes
+    }
+
+    fn decide(&mut self, state: &SimState) -> f64 {
+        let position = (
+            state.hands[0].bits(),
+            state.hands[1].bits(),
+            state.pool.bits(),
+            state.who_next,
+        );
+        if let Some(&value) = self.memo.get(&position) {
+            return value;
+        }
+
+        let sign = FaceUpSolver::sign(state.who_next);
+        let value = sign
+            * state
+                .legal_actions(state.who_next)
+                .collect_vec()
+                .into_iter()
+                .map(|action| sign * self.ask_value(state, action))
+                .fold(f64::MIN, f64::max);
+
+        self.memo.insert(position, value);
+        value
+    }
+
+    /// The value for player 0 of [`SimState::who_next`] asking `action`.
+    fn ask_value(&mut self, state: &SimState, action: Action) -> f64 {
+        let draws = if state.fishes(action) {
+            state.pool.iter().map(Some).collect_vec()
+        } else {
+            vec![None]
+        };
+        let outcomes = draws.len() as f64;
+        draws
+            .into_iter()
+            .map(|drawn| {
+                let mut next = state.clone();
+                next.ask(action, drawn);
+                FaceUpSolver::lead(&next) - FaceUpSolver::lead(state) + self.settle(next)
+            })
+            .sum::<f64>()
+            / outcomes
+    }
+}
@@ -1,70 +1,207 @@
-use core::num;
-use std::collections::HashMap;
 pub use std::fmt::Debug;
 
-use serde::Serialize;
-use typeshare::typeshare;
+use fastrand::Rng;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
 
 use crate::{
-    cards::{Card, Rank, Ranks},
-    random_strat::Random,
-    wiki_strat::Wiki,
+    belief_strat::Beliefs,
+    cards::{Card, Cards, Rank, Ranks},
+    error::GameError,
+    knowledge::PublicKnowledge,
+    registry::StrategyRegistry,
+    rules::RuleSet,
     Player,
 };
 
+/// A bot that can sit at a seat. Object safe, so that the engine can hold any
+/// strategy as a [`Strat`].
 pub trait Strategy: Debug {
-    fn init(pid: PlayerId) -> Self;
     fn deal_card(&mut self, ctx: &Context, card: Card);
     fn action(&mut self, ctx: &Context) -> Option<Action>;
     fn react(&mut self, ctx: &Context, res: Announcement);
+    /// Where the strategy currently thinks the unseen cards are, for
+    /// strategies that keep track.
+    fn beliefs(&self) -> Option<&Beliefs> {
+        None
+    }
+    /// Whether to be shown every hand through [`Context::hands`] when asked
+    /// for an action. Only for benchmarking: such a strategy is cheating, and
+    /// only plays in games that [allow
+    /// it](crate::Game::allow_full_information).
+    fn sees_all_hands(&self) -> bool {
+        false
+    }
+    /// Whether the seat is played from outside the engine, such as by a
+    /// person. The game then waits for [`Game::submit_action`] instead of
+    /// calling [`Strategy::action`].
+    ///
+    /// [`Game::submit_action`]: crate::Game::submit_action
+    fn is_external(&self) -> bool {
+        false
+    }
+}
+
+/// A strategy that can be created by name through a
+/// [`StrategyRegistry`](crate::registry::StrategyRegistry), and saved and
+/// restored along with a game.
+pub trait ConfigurableStrategy: Strategy + Clone + Serialize + DeserializeOwned + 'static {
+    type Config: DeserializeOwned + Default;
+
+    fn init(pid: PlayerId, config: Self::Config) -> Self;
+}
+
+/// The object safe part of [`ConfigurableStrategy`], implemented for all of
+/// them.
+pub trait DynStrategy: Strategy {
+    fn clone_box(&self) -> Box<dyn DynStrategy>;
+    fn save(&self) -> Result<Value, serde_json::Error>;
+}
+
+impl<T: ConfigurableStrategy> DynStrategy for T {
+    fn clone_box(&self) -> Box<dyn DynStrategy> {
+        Box::new(self.clone())
+    }
+    fn save(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
 }
 
-pub enum StratBuilder {
-    Random,
-    Wiki,
+/// Names a registered strategy, and the configuration to create it with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+pub struct StratBuilder {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    #[cfg_attr(feature = "wasm", tsify(type = "unknown"))]
+    pub config: Value,
 }
 
 impl StratBuilder {
-    pub fn init(self, pid: PlayerId) -> Strat {
-        match self {
-            StratBuilder::Random => Strat::Random(Random::init(pid)),
-            StratBuilder::Wiki => Strat::Wiki(Wiki::init(pid)),
+    pub fn new(name: impl Into<String>) -> StratBuilder {
+        StratBuilder {
+            name: name.into(),
+            config: Value::Null,
         }
     }
+    pub fn with_config(mut self, config: Value) -> StratBuilder {
+        self.config = config;
+        self
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Creates the strategy from the [global registry](StrategyRegistry::global).
+    pub fn init(self, pid: PlayerId) -> Result<Strat, GameError> {
+        StrategyRegistry::global().build(self, pid)
+    }
 }
 
+/// A strategy sitting at a seat, along with what it was built from.
 #[derive(Debug)]
-pub enum Strat {
-    Random(Random),
-    Wiki(Wiki),
+pub struct Strat {
+    builder: StratBuilder,
+    strategy: Box<dyn DynStrategy>,
+}
+
+impl Clone for Strat {
+    fn clone(&self) -> Self {
+        Strat {
+            builder: self.builder.clone(),
+            strategy: self.strategy.clone_box(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedStrat {
+    #[serde(flatten)]
+    builder: StratBuilder,
+    state: Value,
+}
+
+impl Serialize for Strat {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SavedStrat {
+            builder: self.builder.clone(),
+            state: self.strategy.save().map_err(serde::ser::Error::custom)?,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Restores the strategy through the [global registry](StrategyRegistry::global).
+impl<'de> Deserialize<'de> for Strat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let saved = SavedStrat::deserialize(deserializer)?;
+        StrategyRegistry::global()
+            .restore(saved.builder, saved.state)
+            .map_err(serde::de::Error::custom)
+    }
 }
 
 impl Strat {
+    pub fn new(builder: StratBuilder, strategy: Box<dyn DynStrategy>) -> Strat {
+        Strat { builder, strategy }
+    }
     pub fn random() -> StratBuilder {
-        StratBuilder::Random
+        StratBuilder::new("random")
     }
     pub fn wiki() -> StratBuilder {
-        StratBuilder::Wiki
+        StratBuilder::new("wiki")
+    }
+    pub fn memory() -> StratBuilder {
+        StratBuilder::new("memory")
+    }
+    pub fn bayes() -> StratBuilder {
+        StratBuilder::new("bayes")
+    }
+    pub fn ismcts() -> StratBuilder {
+        StratBuilder::new("ismcts")
+    }
+    pub fn pimc() -> StratBuilder {
+        StratBuilder::new("pimc")
+    }
+    pub fn human() -> StratBuilder {
+        StratBuilder::new("human")
+    }
+    pub fn name(&self) -> &str {
+        self.builder.name()
+    }
+    /// The builder this strategy was created from.
+    pub fn builder(&self) -> &StratBuilder {
+        &self.builder
     }
     pub fn deal_card(&mut self, ctx: &Context, card: Card) {
-        match self {
-            Strat::Random(s) => s.deal_card(ctx, card),
-            Strat::Wiki(s) => s.deal_card(ctx, card),
-        }
+        self.strategy.deal_card(ctx, card)
     }
 
     pub fn action(&mut self, ctx: &Context) -> Option<Action> {
-        match self {
-            Strat::Random(s) => s.action(ctx),
-            Strat::Wiki(s) => s.action(ctx),
-        }
+        self.strategy.action(ctx)
     }
 
     pub fn react(&mut self, ctx: &Context, a: Announcement) {
-        match self {
-            Strat::Random(s) => s.react(ctx, a),
-            Strat::Wiki(s) => s.react(ctx, a),
-        }
+        self.strategy.react(ctx, a)
+    }
+
+    pub fn beliefs(&self) -> Option<&Beliefs> {
+        self.strategy.beliefs()
+    }
+
+    pub fn sees_all_hands(&self) -> bool {
+        self.strategy.sees_all_hands()
+    }
+
+    pub fn is_external(&self) -> bool {
+        self.strategy.is_external()
     }
 }
 
@@ -82,18 +219,47 @@ impl Default for PublicPlayerInfo {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Context {
+    #[serde(skip)]
     players: Vec<PublicPlayerInfo>,
+    #[serde(
+        serialize_with = "crate::rng_to_state",
+        deserialize_with = "crate::rng_from_state"
+    )]
+    rng: Rng,
+    rules: RuleSet,
+    knowledge: PublicKnowledge,
+    #[serde(skip)]
+    hands: Option<Vec<Cards>>,
+}
+
+/// Copies the generator state exactly, so the copy draws what the original
+/// would have.
+impl Clone for Context {
+    fn clone(&self) -> Self {
+        Context {
+            players: self.players.clone(),
+            rng: Rng::with_seed(self.rng.get_seed()),
+            rules: self.rules,
+            knowledge: self.knowledge.clone(),
+            hands: self.hands.clone(),
+        }
+    }
 }
 
 impl Context {
-    pub fn new(num_players: usize) -> Self {
+    pub fn new(num_players: usize, starting_cards: u32, rng: Rng) -> Self {
         Context {
             players: vec![Default::default(); num_players],
+            rng,
+            rules: RuleSet::house(),
+            knowledge: PublicKnowledge::new(num_players, starting_cards),
+            hands: None,
         }
     }
     pub fn update(&mut self, players: &[Player]) {
+        self.players.resize(players.len(), Default::default());
         for (p, c) in players.iter().zip(self.players.iter_mut()) {
             *c = PublicPlayerInfo {
                 cards_on_hand: p.hand.num() as _,
@@ -107,16 +273,74 @@ impl Context {
             .enumerate()
             .map(|(i, p)| (PlayerId(i as _), *p))
     }
+    /// Every ask `pid` may make while holding `hand`: any rank in their hand,
+    /// asked of any other player who still has cards.
+    pub fn legal_actions(&self, pid: PlayerId, hand: Cards) -> impl Iterator<Item = Action> + '_ {
+        legal_actions(
+            pid,
+            hand,
+            self.players().map(|(pid, p)| (pid, p.cards_on_hand > 0)),
+        )
+    }
+    /// The random number generator strategies should use, so that games
+    /// created with [`Game::with_seed`](crate::Game::with_seed) are reproducible.
+    pub fn rng(&self) -> &Rng {
+        &self.rng
+    }
+    /// Everything that can be deduced about each player's hand from the
+    /// announcements so far.
+    pub fn knowledge(&self) -> &PublicKnowledge {
+        &self.knowledge
+    }
+    /// The rules the game is played by.
+    pub fn rules(&self) -> RuleSet {
+        self.rules
+    }
+    pub(crate) fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+    /// The true hands of every player, but only while a strategy that
+    /// [sees all hands](Strategy::sees_all_hands) is choosing its action.
+    pub fn hands(&self) -> Option<&[Cards]> {
+        self.hands.as_deref()
+    }
+    pub(crate) fn reveal_hands(&mut self, players: &[Player]) {
+        self.hands = Some(players.iter().map(|p| p.hand).collect());
+    }
+    pub(crate) fn conceal_hands(&mut self) {
+        self.hands = None;
+    }
+    pub(crate) fn set_deck(&mut self, deck: Cards) {
+        self.knowledge.set_deck(deck);
+    }
+    pub(crate) fn knowledge_mut(&mut self) -> &mut PublicKnowledge {
+        &mut self.knowledge
+    }
+    pub(crate) fn observe(&mut self, ann: Announcement) {
+        self.knowledge.observe(ann);
+    }
+}
+
+pub(crate) fn legal_actions(
+    pid: PlayerId,
+    hand: Cards,
+    has_cards: impl Iterator<Item = (PlayerId, bool)>,
+) -> impl Iterator<Item = Action> {
+    let ranks = hand.ranks();
+    has_cards
+        .filter(move |&(other, has_cards)| other != pid && has_cards)
+        .flat_map(move |(ask_who, _)| ranks.iter().map(move |ask_for| Action { ask_who, ask_for }))
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
 pub struct Action {
     pub ask_who: PlayerId,
     pub ask_for: Rank,
 }
 
-#[typeshare]
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 #[serde(tag = "type", content = "content")]
 pub enum Announcement {
     Action {
@@ -129,15 +353,44 @@ pub enum Announcement {
         player: PlayerId,
         book: Rank,
     },
+    /// Every player has been dealt their starting hand.
+    DealComplete,
+    /// `player` drew a card from the pool without asking anyone.
+    Drew {
+        player: PlayerId,
+    },
+    /// The only player left with cards took the `count` cards left in the
+    /// pool.
+    PoolClaimed {
+        player: PlayerId,
+        count: u32,
+    },
+    TurnSkipped {
+        player: PlayerId,
+        reason: SkipReason,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+#[serde(tag = "type")]
+pub enum SkipReason {
+    /// The player has no cards and may not draw.
+    EmptyHand,
+    /// Nobody else has cards and the pool is empty.
+    NobodyToAsk,
 }
 
-#[typeshare]
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 #[serde(tag = "type", content = "content")]
 pub enum Response {
     GoFish,
     TakeThese { count: u32 },
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
 pub struct PlayerId(pub u32);
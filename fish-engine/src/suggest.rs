@@ -0,0 +1,140 @@
+use itertools::Itertools;
+use serde::Serialize;
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+use crate::{
+    error::GameError,
+    sim::SimState,
+    strategy::{Action, PlayerId, StratBuilder},
+    Game,
+};
+
+/// How many deals to sample when estimating the chance that an ask succeeds.
+const HINT_DEALS: usize = 200;
+
+const RANK_NAMES: [&str; 13] = [
+    "aces", "twos", "threes", "fours", "fives", "sixes", "sevens", "eights", "nines", "tens",
+    "jacks", "queens", "kings",
+];
+
+/// The ask a strategy would make in someone's seat, and why.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi))]
+pub struct Suggestion {
+    pub action: Action,
+    pub explanation: Explanation,
+}
+
+/// What was known about the suggested ask when it was made.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+pub struct Explanation {
+    /// How many cards of the rank the asking player holds.
+    pub held: u32,
+    /// The fewest cards of the rank the asked player is known to hold.
+    pub known: u32,
+    /// Every other player known to hold the rank.
+    pub known_holders: Vec<PlayerId>,
+    /// How often the asked player holds the rank, over deals that agree with
+    /// everything announced so far.
+    pub catch_chance: f64,
+    /// How many cards the strategy expected to get, if it keeps beliefs.
+    pub expected: Option<f64>,
+    /// All of the above, in words. Players are numbered from 1.
+    pub summary: String,
+}
+
+impl Game {
+    /// Asks the strategy registered as `strategy` what it would do in seat
+    /// `pid`. The strategy is built fresh, handed the seat's cards, and
+    /// decides from a copy of the public knowledge, so the game is left
+    /// untouched.
+    pub fn suggest(&self, pid: PlayerId, strategy: &str) -> Result<Suggestion, GameError> {
+        match self.awaits_action() {
+            None => return Err(GameError::NoActionExpected),
+            Some(who) if who != pid => return Err(GameError::NotYourTurn { player: pid, who }),
+            Some(_) => {}
+        }
+
+        let mut strat = StratBuilder::new(strategy).init(pid)?;
+        if strat.sees_all_hands() || strat.is_external() {
+            return Err(GameError::InvalidConfig(format!(
+                "strategy {strategy:?} cannot give hints"
+            )));
+        }
+
+        let mut ctx = self.ctx.clone();
+        ctx.update(&self.players);
+        let hand = self.players[pid.0 as usize].hand;
+        for c in hand.iter() {
+            strat.deal_card(&ctx, c);
+        }
+        let action = strat
+            .action(&ctx)
+            .ok_or(GameError::NoAction { player: pid })?;
+        self.check_action(pid, action)?;
+
+        let Action { ask_who, ask_for } = action;
+        let knowledge = ctx.knowledge();
+        let held = hand.intersection(ask_for.in_all_suits()).num();
+        let known = knowledge.at_least(ask_who, ask_for);
+        let known_holders = knowledge
+            .known_holders(ask_for)
+            .filter(|&holder| holder != pid)
+            .collect_vec();
+        let catch_chance = if known > 0 {
+            1.0
+        } else {
+            let catches = (0..HINT_DEALS)
+                .filter(|_| {
+                    let deal = SimState::sample(&ctx, pid, hand, ctx.rng());
+                    !deal.hands[ask_who.0 as usize]
+                        .intersection(ask_for.in_all_suits())
+                        .is_empty()
+                })
+                .count();
+            catches as f64 / HINT_DEALS as f64
+        };
+        let expected = strat
+            .beliefs()
+            .map(|beliefs| beliefs.expected(ask_who, ask_for));
+
+        let rank = RANK_NAMES[ask_for as usize];
+        let mut summary = format!(
+            "Ask player {} for {rank}, holding {held} of them. ",
+            ask_who.0 + 1
+        );
+        if known > 0 {
+            summary += &format!("They are known to hold at least {known}.");
+        } else {
+            summary += &format!(
+                "They hold some in {:.0}% of the deals that fit what has been announced.",
+                catch_chance * 100.0
+            );
+        }
+        let others = known_holders
+            .iter()
+            .filter(|&&holder| holder != ask_who)
+            .map(|holder| holder.0 + 1)
+            .join(", ");
+        if !others.is_empty() {
+            summary += &format!(" Also known to hold {rank}: player {others}.");
+        }
+        if let Some(expected) = expected {
+            summary += &format!(" {strategy} expects to get {expected:.1} cards.");
+        }
+
+        Ok(Suggestion {
+            action,
+            explanation: Explanation {
+                held,
+                known,
+                known_holders,
+                catch_chance,
+                expected,
+                summary,
+            },
+        })
+    }
+}
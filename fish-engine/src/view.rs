@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
+use tsify::Tsify;
+
+use crate::{
+    cards::{Cards, Ranks},
+    error::GameError,
+    strategy::{Announcement, PlayerId},
+    Game, GameStage,
+};
+
+/// What one seat is allowed to know about the game: its own hand, and only
+/// public information about everyone else.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi))]
+pub struct PlayerView {
+    pub me: PlayerId,
+    pub hand: Cards,
+    /// Public information about every seat, including `me`.
+    pub players: Vec<SeatView>,
+    pub pool_size: u32,
+    pub announcements: Vec<Announcement>,
+    pub stage: GameStage,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify))]
+pub struct SeatView {
+    pub cards_on_hand: u32,
+    #[serde(
+        serialize_with = "crate::ranks_to_vec",
+        deserialize_with = "crate::ranks_from_vec"
+    )]
+    pub books: Ranks,
+}
+
+/// How far a reader of [`Game::events_since`] has got.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi, from_wasm_abi))]
+pub struct EventCursor {
+    timeline: u32,
+    seen: usize,
+}
+
+/// The announcements a reader has not seen yet, along with the parts of a
+/// [`PlayerView`] that are cheap to send every step.
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "wasm", derive(Tsify), tsify(into_wasm_abi))]
+pub struct EventFeed {
+    /// Pass this back to get what happens next.
+    pub cursor: EventCursor,
+    /// Where `events` go in the list of announcements. Anything the reader
+    /// has from here on was undone and should be dropped.
+    pub first: usize,
+    pub events: Vec<Announcement>,
+    pub me: PlayerId,
+    pub hand: Cards,
+    pub players: Vec<SeatView>,
+    pub pool_size: u32,
+    pub stage: GameStage,
+}
+
+impl Game {
+    pub fn view_for(&self, pid: PlayerId) -> Result<PlayerView, GameError> {
+        Ok(PlayerView {
+            me: pid,
+            hand: self.player(pid)?.hand,
+            players: self.seat_views(),
+            pool_size: self.pool.num(),
+            announcements: self.announcements.clone(),
+            stage: self.stage.clone(),
+        })
+    }
+    /// What seat `pid` gets to see of everything that happened after
+    /// `cursor`. Start from `EventCursor::default()`.
+    pub fn events_since(&self, pid: PlayerId, cursor: EventCursor) -> Result<EventFeed, GameError> {
+        let hand = self.player(pid)?.hand;
+        let timeline = self.history.as_ref().map_or(0, |h| h.timeline());
+        let first = if cursor.timeline == timeline {
+            cursor.seen.min(self.announcements.len())
+        } else {
+            0
+        };
+
+        Ok(EventFeed {
+            cursor: EventCursor {
+                timeline,
+                seen: self.announcements.len(),
+            },
+            first,
+            events: self.announcements[first..].to_vec(),
+            me: pid,
+            hand,
+            players: self.seat_views(),
+            pool_size: self.pool.num(),
+            stage: self.stage.clone(),
+        })
+    }
+    fn seat_views(&self) -> Vec<SeatView> {
+        self.players
+            .iter()
+            .map(|p| SeatView {
+                cards_on_hand: p.hand.num(),
+                books: p.books,
+            })
+            .collect()
+    }
+}
@@ -1,13 +1,13 @@
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     cards::{Card, Cards, Rank},
     common_strat::update_hand_on_announcement,
-    strategy::{Action, Announcement, Context, PlayerId, Strategy},
+    strategy::{Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strategy},
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Wiki {
     pid: PlayerId,
     hand: Cards,
@@ -15,15 +15,19 @@ pub struct Wiki {
     next_rank: Rank,
 }
 
-impl Strategy for Wiki {
-    fn init(pid: PlayerId) -> Self {
+impl ConfigurableStrategy for Wiki {
+    type Config = ();
+
+    fn init(pid: PlayerId, _config: ()) -> Self {
         Wiki {
             pid,
             hand: Cards::empty(),
             next_rank: Rank::RA,
         }
     }
+}
 
+impl Strategy for Wiki {
     fn deal_card(&mut self, _ctx: &Context, card: Card) {
         self.next_rank = card.rank();
         self.hand = self.hand.add(card);
@@ -31,6 +35,7 @@ impl Strategy for Wiki {
 
     fn action(&mut self, ctx: &Context) -> Option<Action> {
         let ask_who = *crate::pick(
+            ctx.rng(),
             &ctx.players()
                 .filter_map(|(pid, p)| {
                     if pid != self.pid && p.cards_on_hand > 0 {
@@ -0,0 +1,169 @@
+use fish_engine::{
+    cards::{Card, Cards, Rank},
+    error::GameError,
+    registry::StrategyRegistry,
+    strategy::{
+        Action, Announcement, ConfigurableStrategy, Context, PlayerId, Strat, StratBuilder,
+        Strategy,
+    },
+    view::EventCursor,
+    Game,
+};
+use serde::{Deserialize, Serialize};
+
+fn awaiting_human() -> Game {
+    let mut game = Game::with_seed(1, 5, [Strat::human(), Strat::random()]).unwrap();
+    while game.awaits_action().is_none() {
+        game.step().unwrap();
+    }
+    game
+}
+
+#[test]
+fn asking_a_missing_seat_is_an_error() {
+    let mut game = awaiting_human();
+    let action = Action {
+        ask_who: PlayerId(9),
+        ask_for: Rank::R2,
+    };
+
+    assert_eq!(
+        game.submit_action(PlayerId(0), action).unwrap_err(),
+        GameError::UnknownPlayer {
+            player: PlayerId(9)
+        }
+    );
+}
+
+#[test]
+fn views_of_a_missing_seat_are_errors() {
+    let game = awaiting_human();
+    let missing = GameError::UnknownPlayer {
+        player: PlayerId(7),
+    };
+
+    assert_eq!(game.legal_actions(PlayerId(7)).err(), Some(missing.clone()));
+    assert_eq!(game.view_for(PlayerId(7)).err(), Some(missing.clone()));
+    assert_eq!(
+        game.events_since(PlayerId(7), EventCursor::default()).err(),
+        Some(missing)
+    );
+}
+
+#[test]
+fn only_external_seats_submit_asks() {
+    let mut game = Game::with_seed(1, 5, [Strat::random(), Strat::human()]).unwrap();
+    while game.awaits_action() != Some(PlayerId(0)) {
+        game.step().unwrap();
+    }
+    let action = game.legal_actions(PlayerId(0)).unwrap().next().unwrap();
+
+    assert_eq!(
+        game.submit_action(PlayerId(0), action).unwrap_err(),
+        GameError::NotExternal {
+            player: PlayerId(0)
+        }
+    );
+}
+
+#[test]
+fn asking_yourself_is_an_error() {
+    let mut game = awaiting_human();
+    let ask_for = game.players[0].hand.iter().next().unwrap().rank();
+    let action = Action {
+        ask_who: PlayerId(0),
+        ask_for,
+    };
+
+    assert_eq!(
+        game.submit_action(PlayerId(0), action).unwrap_err(),
+        GameError::AskedSelf {
+            player: PlayerId(0)
+        }
+    );
+}
+
+#[test]
+fn asking_for_a_rank_you_do_not_hold_is_an_error() {
+    let mut game = awaiting_human();
+    let hand = game.players[0].hand;
+    let ask_for = Card::all()
+        .map(Card::rank)
+        .find(|r| hand.intersection(r.in_all_suits()).is_empty())
+        .unwrap();
+    let action = Action {
+        ask_who: PlayerId(1),
+        ask_for,
+    };
+
+    assert_eq!(
+        game.submit_action(PlayerId(0), action).unwrap_err(),
+        GameError::RankNotHeld {
+            player: PlayerId(0),
+            rank: ask_for
+        }
+    );
+}
+
+#[test]
+fn asking_an_empty_hand_is_an_error() {
+    let seats = [Strat::human(), Strat::random(), Strat::random()];
+    let mut game = Game::with_seed(1, 5, seats).unwrap();
+    while game.awaits_action() != Some(PlayerId(0)) {
+        game.step().unwrap();
+    }
+    game.pool = game.pool.union(game.players[2].hand);
+    game.players[2].hand = Cards::empty();
+    let ask_for = game.players[0].hand.iter().next().unwrap().rank();
+    let action = Action {
+        ask_who: PlayerId(2),
+        ask_for,
+    };
+
+    assert_eq!(
+        game.submit_action(PlayerId(0), action).unwrap_err(),
+        GameError::AskedEmptyHand {
+            player: PlayerId(0),
+            asked: PlayerId(2)
+        }
+    );
+}
+
+/// Never makes an ask, whatever it holds.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Mute;
+
+impl ConfigurableStrategy for Mute {
+    type Config = ();
+
+    fn init(_pid: PlayerId, _config: ()) -> Self {
+        Mute
+    }
+}
+
+impl Strategy for Mute {
+    fn deal_card(&mut self, _ctx: &Context, _card: Card) {}
+    fn action(&mut self, _ctx: &Context) -> Option<Action> {
+        None
+    }
+    fn react(&mut self, _ctx: &Context, _ann: Announcement) {}
+}
+
+#[test]
+fn a_strategy_that_does_not_ask_is_an_error() {
+    StrategyRegistry::register_global::<Mute>("mute");
+    let mut game = Game::with_seed(1, 5, [StratBuilder::new("mute"), Strat::random()]).unwrap();
+    let err = loop {
+        match game.step() {
+            Ok(_) => assert!(!game.stage.is_done(), "the game ended without an ask"),
+            Err(err) => break err,
+        }
+    };
+
+    assert_eq!(
+        err,
+        GameError::NoAction {
+            player: PlayerId(0)
+        }
+    );
+}
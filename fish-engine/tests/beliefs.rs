@@ -0,0 +1,22 @@
+use fish_engine::{cards::Cards, strategy::Strat, Game};
+
+#[test]
+fn beliefs_stay_within_a_reduced_deck() {
+    for seed in 0..20 {
+        let mut game = Game::with_seed(seed, 3, [Strat::bayes(), Strat::bayes(), Strat::wiki()])
+            .unwrap()
+            .with_deck(Cards::all_bounded(3).unwrap())
+            .unwrap();
+        while !game.stage.is_done() {
+            game.step().unwrap();
+            for p in &game.players {
+                let Some(beliefs) = p.strategy.beliefs() else {
+                    continue;
+                };
+                for row in beliefs.players.iter().chain([&beliefs.pool]) {
+                    assert!(row[3..].iter().all(|&expected| expected == 0.0));
+                }
+            }
+        }
+    }
+}
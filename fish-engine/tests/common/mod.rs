@@ -0,0 +1,11 @@
+use fish_engine::strategy::{Strat, StratBuilder};
+
+/// A table of the cheaper fair strategies, one of each.
+pub fn seats() -> Vec<StratBuilder> {
+    vec![
+        Strat::wiki(),
+        Strat::random(),
+        Strat::memory(),
+        Strat::bayes(),
+    ]
+}
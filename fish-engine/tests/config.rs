@@ -0,0 +1,81 @@
+use fish_engine::{
+    config::GameConfig,
+    error::GameError,
+    strategy::{Strat, StratBuilder},
+    Game,
+};
+use serde_json::json;
+
+fn config(seats: Vec<StratBuilder>, starting_cards: u32) -> GameConfig {
+    GameConfig {
+        seats,
+        starting_cards,
+        rules: Default::default(),
+        seed: Some(1),
+    }
+}
+
+/// The message of the error that setting up `config` gives.
+fn error(config: GameConfig) -> String {
+    match Game::from_config(config) {
+        Ok(_) => panic!("the game was set up"),
+        Err(err @ GameError::InvalidConfig(_)) => err.to_string(),
+        Err(err) => panic!("unexpected error: {err}"),
+    }
+}
+
+#[test]
+fn unknown_strategies_are_named() {
+    let err = error(config(vec![Strat::wiki(), StratBuilder::new("sharp")], 5));
+    assert!(err.contains("unknown strategy \"sharp\""), "{err}");
+}
+
+#[test]
+fn bad_seat_configurations_are_named() {
+    let seats = vec![
+        Strat::wiki(),
+        Strat::bayes().with_config(json!({ "reveal_cost": "lots" })),
+    ];
+    let err = error(config(seats, 5));
+    assert!(err.contains("bad config for \"bayes\""), "{err}");
+}
+
+#[test]
+fn a_game_needs_two_players() {
+    let err = error(config(vec![Strat::wiki()], 5));
+    assert!(err.contains("at least 2 players, got 1"), "{err}");
+}
+
+#[test]
+fn the_deck_must_go_around() {
+    let err = error(config(vec![Strat::wiki(); 3], 18));
+    assert!(
+        err.contains("cannot deal 18 cards to each of 3 players from a deck of 52"),
+        "{err}"
+    );
+}
+
+#[test]
+fn unknown_fields_are_rejected() {
+    let config = json!({
+        "seats": [{ "name": "wiki" }, { "name": "random" }],
+        "starting_cards": 5,
+        "starting_hand": 7,
+    });
+    let err = serde_json::from_value::<GameConfig>(config).unwrap_err();
+    assert!(
+        err.to_string().contains("unknown field `starting_hand`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn good_configurations_set_up_games() {
+    let seats = vec![
+        Strat::wiki(),
+        Strat::bayes().with_config(json!({ "reveal_cost": 0.5 })),
+    ];
+    let game = Game::from_config(config(seats, 5)).unwrap();
+    assert_eq!(game.players.len(), 2);
+    assert_eq!(game.seed(), 1);
+}
@@ -0,0 +1,39 @@
+use fish_engine::{
+    error::GameError, oracle_strat::Oracle, registry::StrategyRegistry, replay::Replay,
+    strategy::StratBuilder, Game,
+};
+
+fn oracle_game() -> Game {
+    StrategyRegistry::register_global::<Oracle>("oracle");
+    Game::with_seed(
+        2,
+        5,
+        [StratBuilder::new("oracle"), StratBuilder::new("wiki")],
+    )
+    .unwrap()
+}
+
+#[test]
+fn seeing_every_hand_must_be_allowed() {
+    let mut game = oracle_game();
+    let err = loop {
+        match game.step() {
+            Ok(_) if game.stage.is_done() => panic!("the oracle played unnoticed"),
+            Ok(_) => {}
+            Err(err) => break err,
+        }
+    };
+    assert!(matches!(err, GameError::FullInformation { .. }));
+}
+
+#[test]
+fn allowed_full_information_is_replayed() {
+    let mut game = oracle_game().allow_full_information();
+    while !game.stage.is_done() {
+        game.step().unwrap();
+    }
+
+    let replay = Replay::from_code(&Replay::record(&game).to_code()).unwrap();
+    assert!(replay.full_information);
+    assert_eq!(replay.play().unwrap().announcements, game.announcements);
+}
@@ -0,0 +1,77 @@
+use fish_engine::{
+    strategy::{PlayerId, Strat},
+    view::EventCursor,
+    Game,
+};
+
+fn game() -> Game {
+    let mut game = Game::with_seed(6, 5, [Strat::wiki(), Strat::random(), Strat::bayes()]).unwrap();
+    game.enable_history();
+    game
+}
+
+fn steps(game: &mut Game, n: usize) {
+    for _ in 0..n {
+        game.step().unwrap();
+    }
+}
+
+#[test]
+fn seeking_back_and_stepping_plays_the_same_game() {
+    let mut original = game();
+    while !original.stage.is_done() {
+        original.step().unwrap();
+    }
+
+    let mut rewound = game();
+    while !rewound.stage.is_done() {
+        rewound.step().unwrap();
+    }
+    assert!(rewound.seek(10));
+    assert_eq!(rewound.history().unwrap().cursor(), 10);
+    while !rewound.stage.is_done() {
+        rewound.step().unwrap();
+    }
+
+    assert_eq!(rewound.announcements, original.announcements);
+}
+
+#[test]
+fn undo_and_redo_move_one_step() {
+    let mut game = game();
+    assert!(!game.undo());
+    steps(&mut game, 20);
+    let after = serde_json::to_string(&game).unwrap();
+    steps(&mut game, 1);
+    let latest = serde_json::to_string(&game).unwrap();
+
+    assert!(game.undo());
+    assert_eq!(serde_json::to_string(&game).unwrap(), after);
+    assert!(game.redo());
+    assert_eq!(serde_json::to_string(&game).unwrap(), latest);
+    assert!(!game.redo());
+    assert_eq!(game.history().unwrap().len(), 22);
+}
+
+#[test]
+fn a_new_step_after_undoing_starts_the_feed_over() {
+    let mut game = game();
+    steps(&mut game, 20);
+    let cursor = game
+        .events_since(PlayerId(0), EventCursor::default())
+        .unwrap()
+        .cursor;
+    let seen = game.announcements.len();
+    assert!(seen > 0);
+
+    steps(&mut game, 1);
+    let feed = game.events_since(PlayerId(0), cursor).unwrap();
+    assert_eq!(feed.first, seen);
+
+    assert!(game.undo());
+    assert!(game.undo());
+    steps(&mut game, 1);
+    let feed = game.events_since(PlayerId(0), cursor).unwrap();
+    assert_eq!(feed.first, 0);
+    assert_eq!(feed.events, game.announcements);
+}
@@ -0,0 +1,71 @@
+use fish_engine::{
+    cards::Rank,
+    knowledge::PublicKnowledge,
+    rules::{EndCondition, RuleSet},
+    strategy::{Announcement, PlayerId, Response, Strat},
+    Game,
+};
+use strum::IntoEnumIterator;
+
+fn ask(response: Response) -> PublicKnowledge {
+    let mut knowledge = PublicKnowledge::new(3, 5);
+    knowledge.observe(Announcement::DealComplete);
+    knowledge.observe(Announcement::Action {
+        player_asking: PlayerId(0),
+        player_asked: PlayerId(1),
+        asked_for: Rank::R7,
+        response,
+    });
+    knowledge
+}
+
+#[test]
+fn a_catch_shows_the_asker_holds_one_more() {
+    let knowledge = ask(Response::TakeThese { count: 2 });
+
+    assert_eq!(knowledge.at_least(PlayerId(0), Rank::R7), 3);
+    assert!(knowledge.has_none(PlayerId(1), Rank::R7));
+    assert_eq!(knowledge.at_least(PlayerId(2), Rank::R7), 0);
+    assert!(!knowledge.has_none(PlayerId(2), Rank::R7));
+}
+
+#[test]
+fn going_fishing_shows_the_asked_player_has_none() {
+    let knowledge = ask(Response::GoFish);
+
+    assert_eq!(knowledge.at_least(PlayerId(0), Rank::R7), 1);
+    assert!(knowledge.has_none(PlayerId(1), Rank::R7));
+    assert_eq!(knowledge.pool_size(), 52 - 15 - 1);
+}
+
+#[test]
+fn what_is_known_is_true() {
+    for bits in 0..16u8 {
+        let rules = RuleSet {
+            another_turn_on_catch: bits & 1 != 0,
+            another_turn_on_lucky_fish: bits & 2 != 0,
+            draw_when_empty: bits & 4 != 0,
+            end_condition: if bits & 8 != 0 {
+                EndCondition::AllBooks
+            } else {
+                EndCondition::LastHandTakesPool
+            },
+        };
+        let seats = [Strat::wiki(), Strat::random(), Strat::memory()];
+        let mut game = Game::with_seed(bits as u64, 5, seats)
+            .unwrap()
+            .with_rules(rules);
+        while !game.stage.is_done() {
+            game.step().unwrap();
+            let knowledge = game.knowledge();
+            for (pid, p) in game.players.iter().enumerate() {
+                let pid = PlayerId(pid as _);
+                for rank in Rank::iter() {
+                    let held = p.hand.intersection(rank.in_all_suits()).num();
+                    assert!(held >= knowledge.at_least(pid, rank), "{rules:?}");
+                    assert!(held == 0 || !knowledge.has_none(pid, rank), "{rules:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use std::{cell::RefCell, rc::Rc};
+
+use fish_engine::{
+    cards::{Card, Rank},
+    observer::GameObserver,
+    outcome::Outcome,
+    strategy::{Announcement, PlayerId, Strat},
+    Game,
+};
+
+#[derive(Default)]
+struct Recorder {
+    dealt: Vec<(PlayerId, Card)>,
+    books: Vec<(PlayerId, Rank)>,
+    turns: Vec<(PlayerId, PlayerId)>,
+    finished: Vec<Outcome>,
+}
+
+impl GameObserver for Recorder {
+    fn card_dealt(&mut self, player: PlayerId, card: Card) {
+        self.dealt.push((player, card));
+    }
+    fn book_completed(&mut self, player: PlayerId, rank: Rank) {
+        self.books.push((player, rank));
+    }
+    fn turn_passed(&mut self, from: PlayerId, to: PlayerId) {
+        self.turns.push((from, to));
+    }
+    fn game_finished(&mut self, outcome: &Outcome) {
+        self.finished.push(outcome.clone());
+    }
+}
+
+#[test]
+fn observers_hear_about_the_whole_game() {
+    let recorder = Rc::new(RefCell::new(Recorder::default()));
+    let mut game =
+        Game::with_seed(8, 5, [Strat::wiki(), Strat::random(), Strat::memory()]).unwrap();
+    game.add_observer(recorder.clone());
+    while !game.stage.is_done() {
+        game.step().unwrap();
+    }
+    game.step().unwrap();
+
+    let recorder = recorder.borrow();
+    assert_eq!(recorder.dealt.len(), 15);
+    for pid in 0..3 {
+        let dealt = recorder.dealt.iter().filter(|(p, _)| p.0 == pid).count();
+        assert_eq!(dealt, 5);
+    }
+
+    let books = game
+        .announcements
+        .iter()
+        .filter_map(|a| match *a {
+            Announcement::GotBook { player, book } => Some((player, book)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!books.is_empty());
+    assert_eq!(recorder.books, books);
+
+    assert!(!recorder.turns.is_empty());
+    assert!(recorder.turns.iter().all(|(from, to)| from != to));
+
+    assert_eq!(recorder.finished, [game.outcome().unwrap()]);
+}
@@ -0,0 +1,66 @@
+use fish_engine::{
+    cards::{Rank, Ranks},
+    outcome::{BookCount, GameResult, Outcome, RankPoints},
+    strategy::PlayerId,
+};
+
+fn books(ranks: &[Rank]) -> Ranks {
+    ranks.iter().fold(Ranks::empty(), |rs, &r| rs | r.into())
+}
+
+#[test]
+fn a_shared_top_score_is_a_tie() {
+    let outcome = Outcome::new(
+        &BookCount,
+        [
+            books(&[Rank::RA, Rank::R2]),
+            books(&[Rank::R3]),
+            books(&[Rank::R4, Rank::R5]),
+        ],
+    );
+
+    assert_eq!(outcome.scores, [2, 1, 2]);
+    assert_eq!(
+        outcome.result,
+        GameResult::Tie(vec![PlayerId(0), PlayerId(2)])
+    );
+    assert_eq!(outcome.winners(), [PlayerId(0), PlayerId(2)]);
+}
+
+#[test]
+fn ties_below_first_place_share_a_standing() {
+    let outcome = Outcome::new(
+        &BookCount,
+        [
+            books(&[Rank::RA]),
+            books(&[Rank::R2, Rank::R3, Rank::R4]),
+            books(&[Rank::R5]),
+            books(&[]),
+        ],
+    );
+
+    assert_eq!(outcome.result, GameResult::Winner(PlayerId(1)));
+    assert_eq!(
+        outcome.standings,
+        [
+            vec![PlayerId(1)],
+            vec![PlayerId(0), PlayerId(2)],
+            vec![PlayerId(3)],
+        ]
+    );
+}
+
+#[test]
+fn rank_points_can_change_the_winner() {
+    let made = [books(&[Rank::RK]), books(&[Rank::RA, Rank::R2])];
+    let mut points = [1; 13];
+    points[Rank::RK as usize] = 5;
+
+    assert_eq!(
+        Outcome::new(&BookCount, made).result,
+        GameResult::Winner(PlayerId(1))
+    );
+    let outcome = Outcome::new(&RankPoints(points), made);
+    assert_eq!(outcome.scores, [5, 2]);
+    assert_eq!(outcome.result, GameResult::Winner(PlayerId(0)));
+}
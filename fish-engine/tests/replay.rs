@@ -0,0 +1,81 @@
+use fish_engine::{replay::Replay, strategy::Strat, Game};
+use serde_json::json;
+
+mod common;
+
+fn play(game: &mut Game, steps: usize) -> usize {
+    let mut taken = 0;
+    while taken < steps && !game.stage.is_done() {
+        game.step().unwrap();
+        taken += 1;
+    }
+    taken
+}
+
+#[test]
+fn replayed_games_continue_like_the_original() {
+    for seed in 0..20 {
+        let mut original = Game::with_seed(seed, 5, common::seats()).unwrap();
+        let steps = play(&mut original, 60);
+
+        let mut replayed = Replay::record(&original).play_to(steps).unwrap();
+        assert_eq!(
+            original.announcements, replayed.announcements,
+            "seed {seed} differs at step {steps}"
+        );
+
+        play(&mut original, usize::MAX);
+        play(&mut replayed, usize::MAX);
+        assert_eq!(
+            original.announcements, replayed.announcements,
+            "seed {seed} differs after continuing"
+        );
+    }
+}
+
+#[test]
+fn codes_round_trip() {
+    let mut game = Game::with_seed(3, 5, common::seats()).unwrap();
+    play(&mut game, usize::MAX);
+    let replay = Replay::record(&game);
+
+    assert_eq!(Replay::from_code(&replay.to_code()).unwrap(), replay);
+}
+
+#[test]
+fn codes_round_trip_with_many_seats() {
+    let mut game = Game::with_seed(3, 1, vec![Strat::random(); 40]).unwrap();
+    play(&mut game, usize::MAX);
+    let replay = Replay::record(&game);
+
+    assert_eq!(Replay::from_code(&replay.to_code()).unwrap(), replay);
+}
+
+#[test]
+fn configured_seats_round_trip_through_codes() {
+    let seats = vec![
+        Strat::bayes().with_config(json!({ "reveal_cost": 0.0 })),
+        Strat::pimc().with_config(json!({ "deals": 2, "playouts": 1 })),
+        Strat::wiki(),
+    ];
+    for seed in 0..5 {
+        let mut game = Game::with_seed(seed, 5, seats.clone()).unwrap();
+        play(&mut game, usize::MAX);
+        let replay = Replay::from_code(&Replay::record(&game).to_code()).unwrap();
+
+        assert_eq!(replay.seats, seats);
+        assert_eq!(replay.play().unwrap().announcements, game.announcements);
+    }
+}
+
+#[test]
+fn codes_with_broken_configurations_are_rejected() {
+    assert!(Replay::from_code("1.5.0.bayes~7b-random.").is_err());
+    assert!(Replay::from_code("1.5.0.bayes~zz-random.").is_err());
+}
+
+#[test]
+fn codes_asking_missing_seats_are_rejected() {
+    assert!(Replay::from_code("1.5.0.random-random.zA").is_err());
+    assert!(Replay::from_code("1.5.0.random-random.2A").is_err());
+}
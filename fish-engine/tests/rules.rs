@@ -0,0 +1,111 @@
+use fastrand::Rng;
+use fish_engine::{
+    cards::{Card, Cards, Rank, Suit},
+    rules::{EndCondition, RuleSet},
+    sim::SimState,
+    strategy::{Action, PlayerId},
+};
+
+fn cards(cards: &[(Rank, Suit)]) -> Cards {
+    cards.iter().fold(Cards::empty(), |cs, &(rank, suit)| {
+        cs.add(Card::new(rank, suit))
+    })
+}
+
+fn ask_for_aces(hands: Vec<Cards>, pool: Cards, rules: RuleSet) -> SimState {
+    let mut state = SimState::new(hands, pool, PlayerId(0), rules);
+    state.apply(
+        Action {
+            ask_who: PlayerId(1),
+            ask_for: Rank::RA,
+        },
+        &Rng::with_seed(0),
+    );
+    state
+}
+
+#[test]
+fn a_catch_keeps_the_turn_only_if_the_rules_say_so() {
+    let hands = || {
+        vec![
+            cards(&[(Rank::RA, Suit::Spades), (Rank::R2, Suit::Spades)]),
+            cards(&[(Rank::RA, Suit::Hearths), (Rank::R3, Suit::Spades)]),
+            cards(&[(Rank::R5, Suit::Spades)]),
+        ]
+    };
+    let pool = cards(&[(Rank::RK, Suit::Spades)]);
+    let catch = RuleSet {
+        another_turn_on_catch: true,
+        ..RuleSet::house()
+    };
+
+    assert_eq!(ask_for_aces(hands(), pool, catch).who_next, PlayerId(0));
+    assert_eq!(
+        ask_for_aces(hands(), pool, RuleSet::house()).who_next,
+        PlayerId(1)
+    );
+}
+
+#[test]
+fn a_lucky_fish_keeps_the_turn_only_if_the_rules_say_so() {
+    let hands = || {
+        vec![
+            cards(&[(Rank::RA, Suit::Spades), (Rank::R2, Suit::Spades)]),
+            cards(&[(Rank::R3, Suit::Spades)]),
+            cards(&[(Rank::R5, Suit::Spades)]),
+        ]
+    };
+    let pool = cards(&[(Rank::RA, Suit::Hearths)]);
+    let lucky = RuleSet {
+        another_turn_on_lucky_fish: true,
+        ..RuleSet::house()
+    };
+
+    let state = ask_for_aces(hands(), pool, lucky);
+    assert!(state.pool.is_empty());
+    assert_eq!(state.who_next, PlayerId(0));
+    assert_eq!(
+        ask_for_aces(hands(), pool, RuleSet::house()).who_next,
+        PlayerId(1)
+    );
+}
+
+fn empty_hand_turn(rules: RuleSet) -> SimState {
+    let hands = vec![
+        Cards::empty(),
+        cards(&[(Rank::R3, Suit::Spades), (Rank::R4, Suit::Spades)]),
+        cards(&[(Rank::R5, Suit::Spades), (Rank::R6, Suit::Spades)]),
+    ];
+    let pool = cards(&[(Rank::RK, Suit::Spades)]);
+    let mut state = SimState::new(hands, pool, PlayerId(0), rules);
+    state.advance(&Rng::with_seed(0));
+    state
+}
+
+#[test]
+fn an_empty_hand_draws_only_if_the_rules_say_so() {
+    let drawn = empty_hand_turn(RuleSet {
+        draw_when_empty: true,
+        ..RuleSet::house()
+    });
+    assert_eq!(drawn.hands[0], cards(&[(Rank::RK, Suit::Spades)]));
+    assert_eq!(drawn.who_next, PlayerId(0));
+
+    let skipped = empty_hand_turn(RuleSet::house());
+    assert!(skipped.hands[0].is_empty());
+    assert_eq!(skipped.who_next, PlayerId(1));
+}
+
+#[test]
+fn all_books_without_drawing_ends_when_the_hands_run_out() {
+    let rules = RuleSet {
+        end_condition: EndCondition::AllBooks,
+        ..RuleSet::house()
+    };
+    let pool = cards(&[(Rank::RK, Suit::Spades)]);
+    let mut state = SimState::new(vec![Cards::empty(); 2], pool, PlayerId(0), rules);
+    state.advance(&Rng::with_seed(0));
+
+    assert!(state.is_done());
+    assert_eq!(state.pool, pool);
+}
@@ -0,0 +1,30 @@
+use fish_engine::Game;
+
+mod common;
+
+#[test]
+fn saved_games_resume_exactly() {
+    for seed in 0..10 {
+        let mut original = Game::with_seed(seed, 5, common::seats()).unwrap();
+        for _ in 0..40 {
+            if original.stage.is_done() {
+                break;
+            }
+            original.step().unwrap();
+        }
+
+        let saved = serde_json::to_string(&original).unwrap();
+        let mut resumed: Game = serde_json::from_str(&saved).unwrap();
+        assert_eq!(serde_json::to_string(&resumed).unwrap(), saved);
+
+        while !original.stage.is_done() {
+            original.step().unwrap();
+            resumed.step().unwrap();
+        }
+        assert!(resumed.stage.is_done(), "seed {seed} went on longer");
+        assert_eq!(
+            original.announcements, resumed.announcements,
+            "seed {seed} differs after resuming"
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use fish_engine::Game;
+
+mod common;
+
+fn play(seed: u64) -> Game {
+    let mut game = Game::with_seed(seed, 5, common::seats()).unwrap();
+    while !game.stage.is_done() {
+        game.step().unwrap();
+    }
+    game
+}
+
+#[test]
+fn the_same_seed_plays_the_same_game() {
+    for seed in 0..10 {
+        assert_eq!(play(seed).announcements, play(seed).announcements);
+    }
+}
+
+#[test]
+fn different_seeds_play_different_games() {
+    assert_ne!(play(1).announcements, play(2).announcements);
+}
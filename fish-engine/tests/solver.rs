@@ -0,0 +1,82 @@
+use fish_engine::{
+    cards::Cards,
+    rules::RuleSet,
+    sim::SimState,
+    solver::FaceUpSolver,
+    strategy::{Action, Announcement, Strat, StratBuilder},
+    Game,
+};
+
+#[test]
+fn a_single_rank_is_booked_by_whoever_asks_first() {
+    let mut solver = FaceUpSolver::new(RuleSet::default());
+    let value = solver.deal_value(Cards::all_bounded(1).unwrap(), 2);
+    assert_eq!(value, 1.0);
+}
+
+#[test]
+fn three_ranks_between_two_players() {
+    let deck = Cards::all_bounded(3).unwrap();
+    let mut solver = FaceUpSolver::new(RuleSet::default());
+    let value = solver.deal_value(deck, 5);
+
+    assert!((-3.0..=3.0).contains(&value));
+    assert!(solver.positions() > 0);
+    // On a deck this small, asking first is an advantage.
+    assert!(value >= 0.0, "{value}");
+}
+
+/// The average regret of `strategy` over every ask it made, playing both
+/// seats on a three rank deck, judged over the deals it could not tell apart.
+fn mean_regret(strategy: StratBuilder) -> f64 {
+    let deck = Cards::all_bounded(3).unwrap();
+    let mut solver = FaceUpSolver::new(RuleSet::default());
+    let mut total = 0.0;
+    let mut asks = 0;
+    for seed in 0..10 {
+        let mut game = Game::with_seed(seed, 3, [strategy.clone(), strategy.clone()])
+            .unwrap()
+            .with_deck(deck)
+            .unwrap();
+        while !game.stage.is_done() {
+            let deals = game
+                .awaits_action()
+                .and_then(|_| SimState::from_game(&game))
+                .map(|state| {
+                    let deals = FaceUpSolver::info_set(&state, state.who_next, game.knowledge());
+                    assert!(deals.contains(&state));
+                    deals
+                });
+            let prev = game.announcements.len();
+            game.step().unwrap();
+            let Some(deals) = deals else {
+                continue;
+            };
+            for a in &game.announcements[prev..] {
+                if let &Announcement::Action {
+                    player_asked,
+                    asked_for,
+                    ..
+                } = a
+                {
+                    let action = Action {
+                        ask_who: player_asked,
+                        ask_for: asked_for,
+                    };
+                    let regret = solver.info_set_regret(&deals, action);
+                    assert!(regret >= 0.0);
+                    total += regret;
+                    asks += 1;
+                }
+            }
+        }
+    }
+    total / asks as f64
+}
+
+#[test]
+fn searching_regrets_less_than_asking_at_random() {
+    let random = mean_regret(Strat::random());
+    let ismcts = mean_regret(Strat::ismcts());
+    assert!(ismcts < random / 2.0, "ismcts {ismcts}, random {random}");
+}
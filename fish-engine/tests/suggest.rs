@@ -0,0 +1,38 @@
+use fish_engine::{
+    strategy::{PlayerId, Strat},
+    Game, GameStage,
+};
+
+#[test]
+fn suggestions_leave_the_game_alone() {
+    let mut game = Game::with_seed(4, 5, [Strat::human(), Strat::wiki(), Strat::bayes()]).unwrap();
+    while !game.stage.is_done() {
+        let GameStage::AwaitingInput { who } = game.stage else {
+            game.step().unwrap();
+            continue;
+        };
+
+        let before = serde_json::to_string(&game).unwrap();
+        let suggestion = game.suggest(who, "bayes").unwrap();
+        assert_eq!(serde_json::to_string(&game).unwrap(), before);
+        assert!(suggestion
+            .explanation
+            .summary
+            .starts_with(&format!("Ask player {} ", suggestion.action.ask_who.0 + 1)));
+
+        game.submit_action(who, suggestion.action).unwrap();
+    }
+}
+
+#[test]
+fn only_strategies_that_play_fair_give_hints() {
+    let mut game = Game::with_seed(4, 5, [Strat::human(), Strat::wiki()]).unwrap();
+    while game.awaits_action().is_none() {
+        game.step().unwrap();
+    }
+    let who = game.awaits_action().unwrap();
+
+    assert!(game.suggest(who, "human").is_err());
+    assert!(game.suggest(who, "nope").is_err());
+    assert!(game.suggest(PlayerId(1 - who.0), "wiki").is_err());
+}
@@ -2,14 +2,15 @@ mod utils;
 
 use fish_engine::{
     self,
-    cards::{Card, Cards, Rank},
-    random_strat::Random,
-    strategy::{PlayerId, Response, Strat},
-    wiki_strat::Wiki,
-    Game, GameStage, Player,
+    config::GameConfig,
+    outcome::Outcome,
+    replay::Replay,
+    strategy::{Action, PlayerId, Strat},
+    suggest::Suggestion,
+    view::{EventCursor, EventFeed, PlayerView},
+    Game, GameStage, StepOutcome,
 };
 
-use typeshare::typeshare;
 use wasm_bindgen::prelude::*;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
@@ -18,6 +19,12 @@ use wasm_bindgen::prelude::*;
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Action[]")]
+    pub type Actions;
+}
+
 #[wasm_bindgen]
 pub struct Engine {
     game: Game,
@@ -32,16 +39,62 @@ impl Engine {
         let game = Game::new(
             17,
             [
+                Strat::human(),
                 Strat::wiki(),
                 Strat::random(),
-                Strat::random(),
                 // Player::new(Box::new(Random::default())),
                 // Player::new(Box::new(Random::default())),
                 // Player::new(Box::new(Random::default())),
             ],
-        );
+        )
+        .expect("three players can be dealt 17 cards each");
 
-        Engine { game }
+        Engine::with_game(game)
+    }
+
+    /// Sets up a game from a `GameConfig`. Seats played by a person should
+    /// use the `"human"` strategy.
+    pub fn from_config(config: GameConfig) -> Result<Engine, JsValue> {
+        console_error_panic_hook::set_once();
+        let _ = console_log::init_with_level(log::Level::Debug);
+
+        let game = Game::from_config(config).map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        Ok(Engine::with_game(game))
+    }
+
+    /// Rebuilds a game from a code produced by [`Engine::replay_code`].
+    pub fn from_replay(code: &str) -> Result<Engine, JsValue> {
+        console_error_panic_hook::set_once();
+        let _ = console_log::init_with_level(log::Level::Debug);
+
+        let game = Replay::from_code(code)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?
+            .play()
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        Ok(Engine::with_game(game))
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.game.undo()
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.game.redo()
+    }
+
+    /// Jumps to `step` on the timeline, where `0` is the start of the game.
+    pub fn seek(&mut self, step: usize) -> bool {
+        self.game.seek(step)
+    }
+
+    pub fn history_len(&self) -> usize {
+        self.game.history().map_or(0, |h| h.len())
+    }
+
+    pub fn history_cursor(&self) -> usize {
+        self.game.history().map_or(0, |h| h.cursor())
     }
 
     pub fn step(&mut self) {
@@ -49,23 +102,142 @@ impl Engine {
 
         match &self.game.stage {
             GameStage::Dealing { .. } => {
-                self.game.step();
+                if let Err(err) = self.game.step() {
+                    log::error!("{err}");
+                }
             }
-            GameStage::Playing { .. } => {
-                for i in 0..100 {
-                    self.game.step();
-                    if self.game.announcements.len() != pre {
+            GameStage::Playing { .. } => loop {
+                match self.game.step() {
+                    Ok(StepOutcome::Continue) if self.game.announcements.len() == pre => {}
+                    Ok(_) => break,
+                    Err(err) => {
+                        log::error!("{err}");
                         break;
                     }
                 }
-            }
-            GameStage::Done { .. } => {}
+            },
+            GameStage::AwaitingInput { .. } | GameStage::Done { .. } => {}
         }
     }
 
+    /// The seat the game is waiting on to ask, if it is played by a person.
+    pub fn awaiting_input(&self) -> Option<u32> {
+        match self.game.stage {
+            GameStage::AwaitingInput { who } => Some(who.0),
+            _ => None,
+        }
+    }
+
+    /// Every ask `pid` could make. Only for seats played from this page.
+    pub fn legal_actions(&self, pid: u32) -> Result<Actions, JsValue> {
+        let actions = self
+            .game
+            .legal_actions(self.own_seat(pid)?)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?
+            .collect::<Vec<_>>();
+        Ok(serde_wasm_bindgen::to_value(&actions)?.unchecked_into())
+    }
+
+    /// Makes the ask of `pid`.
+    pub fn submit_action(&mut self, pid: u32, action: Action) -> Result<(), JsValue> {
+        self.game
+            .submit_action(PlayerId(pid), action)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        Ok(())
+    }
+
+    /// What the strategy registered as `strategy` would ask in seat `pid`,
+    /// without changing the game. Only for seats played from this page.
+    pub fn suggest(&self, pid: u32, strategy: &str) -> Result<Suggestion, JsValue> {
+        self.game
+            .suggest(self.own_seat(pid)?, strategy)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// The game as seen from seat `pid`, without anyone else's cards. Only
+    /// for seats played from this page.
+    pub fn view_for(&self, pid: u32) -> Result<PlayerView, JsValue> {
+        self.game
+            .view_for(self.own_seat(pid)?)
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    /// What `pid` has not seen since `cursor`, or everything so far when no
+    /// cursor is given. Only for seats played from this page.
+    pub fn events_since(
+        &self,
+        pid: u32,
+        cursor: Option<EventCursor>,
+    ) -> Result<EventFeed, JsValue> {
+        self.game
+            .events_since(self.own_seat(pid)?, cursor.unwrap_or_default())
+            .map_err(|err| JsValue::from_str(&err.to_string()))
+    }
+
+    pub fn replay_code(&self) -> String {
+        Replay::record(&self.game).to_code()
+    }
+
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.game.outcome()
+    }
+}
+
+/// Saving and loading the whole game, every hand and every strategy's state
+/// included, and looking into the strategies. This is for debugging and is
+/// left out unless the `save-state` feature is on, so a page playing the game
+/// cannot look behind the cards.
+#[cfg(feature = "save-state")]
+#[wasm_bindgen]
+impl Engine {
+    /// Resumes a game previously saved with [`Engine::game_state`].
+    pub fn from_state(state: &str) -> Result<Engine, JsValue> {
+        console_error_panic_hook::set_once();
+        let _ = console_log::init_with_level(log::Level::Debug);
+
+        let game =
+            serde_json::from_str(state).map_err(|err| JsValue::from_str(&err.to_string()))?;
+
+        Ok(Engine::with_game(game))
+    }
+
+    /// The whole game as JSON, to be handed back to [`Engine::from_state`].
     pub fn game_state(&self) -> String {
         serde_json::to_string(&self.game).unwrap()
     }
+
+    /// Where the strategy at `pid` thinks the unseen cards are, if it keeps
+    /// track. Its own hand is in there too.
+    pub fn beliefs(&self, pid: u32) -> Result<Option<fish_engine::belief_strat::Beliefs>, JsValue> {
+        let player = self
+            .game
+            .player(PlayerId(pid))
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        Ok(player.strategy.beliefs().cloned())
+    }
+}
+
+impl Engine {
+    fn with_game(mut game: Game) -> Engine {
+        game.enable_history();
+        Engine { game }
+    }
+    /// `pid`, if it is a seat played from outside the engine. The page only
+    /// gets to see what those seats are allowed to know.
+    fn own_seat(&self, pid: u32) -> Result<PlayerId, JsValue> {
+        let pid = PlayerId(pid);
+        let player = self
+            .game
+            .player(pid)
+            .map_err(|err| JsValue::from_str(&err.to_string()))?;
+        if !player.strategy.is_external() {
+            return Err(JsValue::from_str(&format!(
+                "player {} is not played from this page",
+                pid.0
+            )));
+        }
+        Ok(pid)
+    }
 }
 
 impl Default for Engine {
@@ -73,45 +245,3 @@ impl Default for Engine {
         Self::new()
     }
 }
-
-// #[typeshare]
-// pub struct GameState {
-//     pool: Cards,
-//     players: Vec<Player>,
-//     announcements: Vec<Announcement>,
-//     stage: GameStage,
-// }
-
-//   enum GameStage
-//     | { name: "Dealing"; who_next: number }
-//     | { name: "Playing"; who_next: number };
-
-// #[typeshare]
-// pub struct PlayerState {
-//     hand: Cards,
-//     books: Vec<Vec<Card>>,
-// }
-
-// #[typeshare]
-// struct Announcement {
-//     player_asking: PlayerId,
-//     player_asked: PlayerId,
-//     asked_for: Rank,
-//     response: Response,
-// }
-
-//   struct Response
-//     | {
-//         struct:";
-//       }
-//     | {: "TakeThese"; count: number };
-
-// #[wasm_bindgen]
-// pub fn greet() {
-//     console_error_panic_hook::set_once();
-//     console_log::init_with_level(log::Level::Debug);
-
-//     let mut game = Game::new(10, vec![Player::new(Box::new(Random::default()))]);
-
-//     info!("Hello, fish-engine :) {game:?}");
-// }
@@ -1,9 +1,17 @@
-import React, { useEffect, useState } from "react";
+import React, { useEffect, useRef, useState } from "react";
 import "./index.css";
 import * as card from "./Card";
 import * as fish from "fish-wasm";
 import { motion, LayoutGroup, AnimatePresence } from "framer-motion";
-import { Game } from "./types";
+import type {
+  Action,
+  Announcement,
+  Card,
+  GameStage,
+  PlayerId,
+  Rank,
+  Suggestion,
+} from "fish-wasm";
 
 const CARDS =
   "🂡 🂢 🂣 🂤 🂥 🂦 🂧 🂨 🂩 🂪 🂫 🂬 🂭 🂮 🂱 🂲 🂳 🂴 🂵 🂶 🂷 🂸 🂹 🂺 🂻 🂼 🂽 🂾 🃁 🃂 🃃 🃄 🃅 🃆 🃇 🃈 🃉 🃊 🃋 🃌 🃍 🃎 🃑 🃒 🃓 🃔 🃕 🃖 🃗 🃘 🃙 🃚 🃛 🃜 🃝 🃞".split(
@@ -70,13 +78,127 @@ const Hand = ({ hidden, cards }: { hidden?: boolean; cards: Card[] }) => {
   );
 };
 
-const getGameState = (engine: fish.Engine): Game =>
-  JSON.parse(engine.game_state());
+const HiddenHand = ({ count }: { count: number }) => (
+  <Hand hidden cards={[...Array(count).keys()] as Card[]} />
+);
+
+const describe = (announcement: Announcement): string => {
+  switch (announcement.type) {
+    case "DealComplete":
+      return "All hands have been dealt";
+    case "Drew":
+      return `P${announcement.content.player + 1} drew a card`;
+    case "PoolClaimed":
+      return `P${announcement.content.player + 1} took the last ${
+        announcement.content.count
+      } cards from the pool`;
+    case "TurnSkipped":
+      return `P${announcement.content.player + 1} was skipped`;
+    default:
+      return "";
+  }
+};
+
+/** The seat the person in front of the screen is sitting in. */
+const ME = 0;
+
+/** The strategy asked for hints. */
+const HINT_STRATEGY = "pimc";
+
+const whoseTurn = (stage: GameStage): PlayerId | undefined => {
+  switch (stage.name) {
+    case "Playing":
+      return stage.content.who_next;
+    case "AwaitingInput":
+      return stage.content.who;
+    default:
+      return undefined;
+  }
+};
+
+const RANK_NAMES: Record<Rank, string> = {
+  RA: "aces",
+  R2: "twos",
+  R3: "threes",
+  R4: "fours",
+  R5: "fives",
+  R6: "sixes",
+  R7: "sevens",
+  R8: "eights",
+  R9: "nines",
+  R10: "tens",
+  RJ: "jacks",
+  RQ: "queens",
+  RK: "kings",
+};
+
+const AskPicker = ({
+  actions,
+  hint,
+  onAsk,
+  onHint,
+}: {
+  actions: Action[];
+  hint?: Suggestion;
+  onAsk: (action: Action) => void;
+  onHint: () => void;
+}) => (
+  <div className="flex flex-col items-center gap-2">
+    <div className="flex flex-wrap justify-center gap-2">
+      {actions.map((a) => (
+        <button
+          key={`${a.ask_who}-${a.ask_for}`}
+          className={
+            "px-3 py-1 rounded hover:bg-slate-600 " +
+            (hint?.action.ask_who == a.ask_who &&
+            hint?.action.ask_for == a.ask_for
+              ? "bg-green-700"
+              : "bg-slate-700")
+          }
+          onClick={() => onAsk(a)}
+        >
+          Ask P{a.ask_who + 1} for {RANK_NAMES[a.ask_for]}
+        </button>
+      ))}
+      <button
+        className="px-3 py-1 rounded border border-slate-600 hover:bg-slate-800"
+        onClick={onHint}
+      >
+        Hint
+      </button>
+    </div>
+    {hint && (
+      <div className="text-slate-400 text-sm max-w-prose text-center">
+        {hint.explanation.summary}
+      </div>
+    )}
+  </div>
+);
+
+/** Keeps every announcement seen so far, and only asks the engine for what
+ * is new. */
+const useFeed = (engine: fish.Engine) => {
+  const [feed, setFeed] = useState(() => engine.events_since(ME));
+  const [announcements, setAnnouncements] = useState(feed.events);
+  const cursor = useRef(feed.cursor);
+
+  const refresh = () => {
+    const next = engine.events_since(ME, cursor.current);
+    cursor.current = next.cursor;
+    setAnnouncements((seen) => [
+      ...seen.slice(0, next.first),
+      ...next.events,
+    ]);
+    setFeed(next);
+  };
+
+  return { game: { ...feed, announcements }, refresh };
+};
 
 function App() {
   const [engine, setEngine] = useState(() => fish.Engine.new());
-  const game = getGameState(engine);
-  const [_, update] = useState(0);
+  const { game, refresh } = useFeed(engine);
+  const [hint, setHint] = useState<Suggestion>();
 
   const ROUNDS_PER_TICK = 1;
 
@@ -86,7 +208,7 @@ function App() {
         for (let i = 0; i < ROUNDS_PER_TICK; i++) {
           engine.step();
         }
-        update((u) => u + 1);
+        refresh();
       },
       game.stage.name == "Dealing" ? 100 : 100
     );
@@ -155,7 +277,7 @@ function App() {
                     </motion.div>
                   </React.Fragment>
                 );
-              } else {
+              } else if (announcement.type == "GotBook") {
                 const a = announcement.content;
 
                 return (
@@ -191,6 +313,16 @@ function App() {
                     </motion.div>
                   </React.Fragment>
                 );
+              } else {
+                return (
+                  <motion.div
+                    key={id}
+                    layoutId={`left-${id}`}
+                    className="col-span-3 text-slate-400 text-center"
+                  >
+                    {describe(announcement)}
+                  </motion.div>
+                );
               }
             })}
         </div>
@@ -209,10 +341,13 @@ function App() {
                 Player {pid + 1} ({p.books.length})
               </motion.div>
               <div>
-                <Hand cards={p.hand} hidden={pid != 0 && true} />
+                {pid == game.me ? (
+                  <Hand cards={game.hand} />
+                ) : (
+                  <HiddenHand count={p.cards_on_hand} />
+                )}
               </div>
-              {pid == game.stage.content.who_next &&
-                game.stage.name == "Playing" && (
+              {pid == whoseTurn(game.stage) && (
                   <motion.div
                     layoutId="turn"
                     className="text-green-400 text-center text-xs uppercase font-bold"
@@ -224,8 +359,20 @@ function App() {
           ))}
         </div>
         <div>
-          <Hand cards={game.pool} hidden />
+          <HiddenHand count={game.pool_size} />
         </div>
+        {engine.awaiting_input() == ME && (
+          <AskPicker
+            actions={engine.legal_actions(ME)}
+            hint={hint}
+            onAsk={(action) => {
+              engine.submit_action(ME, action);
+              setHint(undefined);
+              refresh();
+            }}
+            onHint={() => setHint(engine.suggest(ME, HINT_STRATEGY))}
+          />
+        )}
       </div>
 
       {/* <div className="p-4 flex">